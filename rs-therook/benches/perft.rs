@@ -0,0 +1,43 @@
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use therook::engine::*;
+
+// Perft exercises move generation, make_move and undo_move and nothing else, so it tracks their
+// speed directly. Run with cargo bench and compare against a baseline with --save-baseline
+fn perft(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("perft");
+    group.sample_size(10);
+
+    for position in PERFT_POSITIONS.iter().take(3) {
+        let (depth, nodes) = position.get_depths(1_000_000).last().unwrap();
+        let mut board = Board::from_fen(position.fen);
+
+        group.throughput(Throughput::Elements(nodes));
+        group.bench_function(format!("{} depth {depth}", position.name), |bencher| {
+            bencher.iter(|| black_box(board.perft(depth)))
+        });
+    }
+
+    group.finish();
+}
+
+fn generate_moves(criterion: &mut Criterion) {
+    let boards = PERFT_POSITIONS
+        .iter()
+        .map(|position| Board::from_fen(position.fen))
+        .collect::<Vec<_>>();
+    let mut moves = MoveList::new();
+
+    criterion.bench_function("generate_moves", |bencher| {
+        bencher.iter(|| {
+            for board in &boards {
+                moves.clear();
+                board.generate_moves(GenType::All, &mut moves);
+                black_box(&moves);
+            }
+        })
+    });
+}
+
+criterion_group!(benches, perft, generate_moves);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use therook::engine::*;
+
+// Any input has to come back as an error rather than a panic, and a position that passes validation
+// has to survive being written out, read back and played on
+fuzz_target!(|data: &[u8]| {
+    let Ok(fen) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = Board::try_from_fen(fen);
+
+    let Ok(mut board) = Board::try_from_fen_strict(fen) else {
+        return;
+    };
+
+    let written = board.to_fen();
+    assert_eq!(Board::from_fen(&written).to_fen(), written);
+
+    for r#move in board.calculate_moves() {
+        board.make_move(r#move);
+        board.undo_move(r#move);
+    }
+
+    assert_eq!(board.to_fen(), written);
+});
@@ -6,11 +6,11 @@ impl std::fmt::Debug for Bitboard {
         let mut lines = String::new();
 
         lines.push_str(&format!("Bitboard: {}\n", u64::from(*self)));
-        lines.push_str(&"  ╔═══╦═══╦═══╦═══╦═══╦═══╦═══╦═══╗\n");
+        lines.push_str("  ╔═══╦═══╦═══╦═══╦═══╦═══╦═══╦═══╗\n");
         for rank in (0..8).rev() {
             lines.push_str(&format!(
                 "{} ║{}\n",
-                format!("{}", vec![1, 2, 3, 4, 5, 6, 7, 8][rank]),
+                rank + 1,
                 (rank * 8..rank * 8 + 8)
                     .map(|i| if self.0 & 1 << i != 0 {
                         String::from("1").on_bright_white()
@@ -21,11 +21,11 @@ impl std::fmt::Debug for Bitboard {
             ));
 
             if rank != 0 {
-                lines.push_str(&"  ╠═══╬═══╬═══╬═══╬═══╬═══╬═══╬═══╣\n");
+                lines.push_str("  ╠═══╬═══╬═══╬═══╬═══╬═══╬═══╬═══╣\n");
             }
         }
-        lines.push_str(&"  ╚═══╩═══╩═══╩═══╩═══╩═══╩═══╩═══╝\n");
-        lines.push_str(&"    A   B   C   D   E   F   G   H  \n");
+        lines.push_str("  ╚═══╩═══╩═══╩═══╩═══╩═══╩═══╩═══╝\n");
+        lines.push_str("    A   B   C   D   E   F   G   H  \n");
 
         write!(f, "{}", lines)
     }
@@ -27,6 +27,9 @@ pub const FILE_H: Bitboard = Bitboard(FILE * 1 << 7);
 pub const DIAGONAL_MAIN: Bitboard = Bitboard(0x8040201008040201);
 pub const ANTIDIAG_MAIN: Bitboard = Bitboard(0x0102040810204080);
 
+pub const LIGHT_SQUARES: Bitboard = Bitboard(0x55AA55AA55AA55AA);
+pub const DARK_SQUARES: Bitboard = Bitboard(!0x55AA55AA55AA55AA);
+
 impl Bitboard {
     pub fn north(self) -> Bitboard {
         self << 8
@@ -88,7 +91,7 @@ impl Bitboard {
         let mut x = self.0;
         x = ((x >> 8) & k1) | ((x & k1) << 8);
         x = ((x >> 16) & k2) | ((x & k2) << 16);
-        x = (x >> 32) | (x << 32);
+        x = x.rotate_left(32);
         x.into()
     }
 }
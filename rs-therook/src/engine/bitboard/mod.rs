@@ -22,6 +22,12 @@ impl Bitboard {
     }
 }
 
+impl Default for Bitboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl From<Bitboard> for u64 {
     fn from(bitboard: Bitboard) -> Self {
         bitboard.0
@@ -1,12 +1,24 @@
 use super::*;
 
-impl Board<'_> {
-    pub fn calculate_moves(&self) -> Vec<Move> {
-        let mut moves: Vec<Move> = vec![];
+impl Board {
+    pub fn calculate_moves(&self) -> MoveList {
+        let mut moves = MoveList::new();
+        self.generate_moves(GenType::All, &mut moves);
+        moves
+    }
 
+    // Appends to the list rather than returning one, so that callers can reuse a list they own.
+    // Each GenType narrows the squares pieces may move to before any move is made up, so the moves
+    // it doesn't want are never generated
+    pub fn generate_moves(&self, gen_type: GenType, moves: &mut MoveList) {
         let color = self.turn;
         let enemy = self.turn.opposite();
 
+        let check_state = self.get_check_state();
+        if gen_type == GenType::Evasions && check_state == CheckState::None {
+            return;
+        }
+
         let friendlies = self.colors[color];
         let enemies = self.colors[enemy];
         let occupancy = friendlies | enemies;
@@ -14,62 +26,63 @@ impl Board<'_> {
 
         let king_square = u8::try_from(self.pieces[color | PieceType::King]).unwrap();
 
-        // Return early because double checks means only King can move
-        if self.check_state[color] == CheckState::Double {
-            let mut attacks =
-                self.computed
-                    .attacks
-                    .get(color, PieceType::King, king_square, occupancy);
-
-            // Don't attack friendly pieces
-            attacks &= !friendlies;
-
-            // Don't allow King to move into attacked squares
-            attacks &= !self.attacks[enemy];
+        let targets = match gen_type {
+            GenType::All | GenType::Evasions => !friendlies,
+            GenType::Captures => enemies,
+            GenType::Quiets | GenType::QuietChecks => !occupancy,
+        };
 
-            for _square in attacks {
-                moves.push(Move::new(king_square, _square, MoveFlag::None));
-            }
-
-            return moves;
+        // Quiet checks land on squares attacking the enemy king, which there's none of without one
+        let enemy_king = self.pieces[enemy | PieceType::King];
+        if gen_type == GenType::QuietChecks && enemy_king.is_none() {
+            return;
         }
 
-        for square in friendlies {
+        // Double checks means only King can move
+        let pieces = if check_state == CheckState::Double {
+            Bitboard::from(king_square)
+        } else {
+            friendlies
+        };
+
+        for square in pieces {
             let bitboard = Bitboard::from(square);
             let piece = self.squares[square as usize].unwrap();
 
             let color = piece.get_color();
             let r#type = piece.get_type();
 
-            let mut attacks = self.computed.attacks.get(color, r#type, square, occupancy);
+            let mut attacks = COMPUTED.attacks.get(color, r#type, square, occupancy);
             let mut can_enpassant = false;
 
-            // Don't attack friendly pieces
-            attacks &= !friendlies;
+            // Don't attack friendly pieces, and only go where this GenType wants. Pawns are
+            // handled below, since they don't move to most of the squares they attack
+            if r#type != PieceType::Pawn {
+                attacks &= targets;
+            }
 
-            // Don't allow King to move into attacked squares
+            // Don't allow King to move into attacked squares. Each target is looked up on its own
+            // with the king taken off the board, so it can't step back along a checking slider
             if r#type == PieceType::King {
-                attacks &= !self.attacks[enemy];
-
-                if state.castling[color | PieceType::King]
-                    && self.check_state[color] == CheckState::None
-                    && self.squares[square as usize + 1].is_none()
-                    && self.squares[square as usize + 2].is_none()
-                    && (self.attacks[enemy] & Bitboard::from(square + 1)).is_none()
-                    && (self.attacks[enemy] & Bitboard::from(square + 2)).is_none()
-                {
-                    attacks |= Bitboard::from(square + 2);
+                let occupancy = occupancy ^ square;
+                for target in attacks {
+                    if self.is_attacked(target, occupancy, enemy) {
+                        attacks ^= target;
+                    }
                 }
 
-                if state.castling[color | PieceType::Queen]
-                    && self.check_state[color] == CheckState::None
-                    && self.squares[square as usize - 1].is_none()
-                    && self.squares[square as usize - 2].is_none()
-                    && self.squares[square as usize - 3].is_none()
-                    && (self.attacks[enemy] & Bitboard::from(square - 1)).is_none()
-                    && (self.attacks[enemy] & Bitboard::from(square - 2)).is_none()
-                {
-                    attacks |= Bitboard::from(square - 2);
+                if gen_type != GenType::Captures {
+                    for castle in [color | PieceType::King, color | PieceType::Queen] {
+                        if let Some(rook_square) = self.get_castling_rook(castle) {
+                            let r#move = Move::new(square, rook_square, MoveFlag::Castle);
+
+                            if self.can_castle(square, rook_square)
+                                && (gen_type != GenType::QuietChecks || self.gives_check(r#move))
+                            {
+                                moves.push(r#move);
+                            }
+                        }
+                    }
                 }
             }
 
@@ -78,22 +91,30 @@ impl Board<'_> {
                 if (attacks & state.enpassant).is_some() {
                     let enpassant_square = u8::try_from(state.enpassant).unwrap();
 
+                    // A diagonal pin of the capturing pawn is handled with the other pin lines
+                    // below, but the captured pawn may be all that stands between the king and an
+                    // enemy bishop or queen
                     let diagonal_pinned = {
-                        let mut pinned = false;
-                        for pin_line in &self.pin_lines[color] {
-                            if (*pin_line & state.enpassant).is_some() {
-                                pinned = true;
-                            }
-                        }
+                        let captured_pawn = (square & 56) + (enpassant_square & 7);
+
+                        let attackers = COMPUTED.attacks.get(
+                            color,
+                            PieceType::Bishop,
+                            king_square,
+                            (occupancy ^ square ^ captured_pawn) | state.enpassant,
+                        );
 
-                        pinned
+                        let enemy_sliders = self.pieces[enemy | PieceType::Bishop]
+                            | self.pieces[enemy | PieceType::Queen];
+
+                        (attackers & enemy_sliders).is_some()
                     };
 
                     let orthogonal_pinned = {
                         let capturing_pawn = square;
                         let captured_pawn = (square & 56) + (enpassant_square & 7);
 
-                        let possible_pinners_without_pawns = self.computed.attacks.get(
+                        let possible_pinners_without_pawns = COMPUTED.attacks.get(
                             color,
                             PieceType::Rook,
                             king_square,
@@ -116,13 +137,12 @@ impl Board<'_> {
                                 // Same file pin
                                 // This is only a pin if the capturing pawn is pinned
                                 // If the captured pawn is pinned, capturing is still allowed because the capturing pawn retains the pin
-                                let possible_pinners_without_capturing_pawn =
-                                    self.computed.attacks.get(
-                                        color,
-                                        PieceType::Rook,
-                                        king_square,
-                                        occupancy ^ capturing_pawn,
-                                    );
+                                let possible_pinners_without_capturing_pawn = COMPUTED.attacks.get(
+                                    color,
+                                    PieceType::Rook,
+                                    king_square,
+                                    occupancy ^ capturing_pawn,
+                                );
 
                                 (possible_pinners_without_capturing_pawn & pinner).is_some()
                             }
@@ -142,28 +162,37 @@ impl Board<'_> {
                     attacks |= state.enpassant;
                 }
 
+                let mut pushes = Bitboard::new();
+
                 if color == PieceColor::White && self.squares[square as usize + 8].is_none() {
-                    attacks |= bitboard << 8;
+                    pushes |= bitboard << 8;
 
                     if square >> 3 == 1 && self.squares[square as usize + 16].is_none() {
-                        attacks |= bitboard << 16;
+                        pushes |= bitboard << 16;
                     }
                 }
 
                 if color == PieceColor::Black && self.squares[square as usize - 8].is_none() {
-                    attacks |= bitboard >> 8;
+                    pushes |= bitboard >> 8;
 
                     if square >> 3 == 6 && self.squares[square as usize - 16].is_none() {
-                        attacks |= bitboard >> 16;
+                        pushes |= bitboard >> 16;
                     }
                 }
+
+                attacks = match gen_type {
+                    GenType::All | GenType::Evasions => attacks | pushes,
+                    // Promoting to a queen is noisy even without a capture
+                    GenType::Captures => attacks | (pushes & (RANK_1 | RANK_8)),
+                    GenType::Quiets | GenType::QuietChecks => pushes,
+                };
             }
 
             // If in check and the piece is not a king
             if r#type != PieceType::King {
-                if let CheckState::Single(attacker) = self.check_state[color] {
+                if let CheckState::Single(attacker) = check_state {
                     // Try to resolve the check by blocking the attack
-                    let mut resolving = self.computed.betweens.get(attacker, king_square);
+                    let mut resolving = COMPUTED.betweens.get(attacker, king_square);
 
                     // Or capturing the attacker
                     resolving |= attacker;
@@ -187,15 +216,34 @@ impl Board<'_> {
             }
 
             // If piece is pinned, only allow moves that keep piece within pin line
-            for pin_line in &self.pin_lines[color] {
-                if (*pin_line & Bitboard::from(square)).is_some() {
-                    attacks &= *pin_line;
+            if (state.pinned & square).is_some() {
+                attacks &= COMPUTED.lines.get(king_square, square);
+            }
+
+            // Only moves landing where the piece attacks the enemy king check it directly. Pieces
+            // that might uncover a slider behind them, pawns that might promote into a checking
+            // piece and the king, which can only check by uncovering, are left to gives_check
+            let mut verify = false;
+            if gen_type == GenType::QuietChecks {
+                let enemy_king_square = u8::try_from(enemy_king).unwrap();
+                let sliders = self.pieces[color | PieceType::Queen]
+                    | self.pieces[color | PieceType::Rook]
+                    | self.pieces[color | PieceType::Bishop];
+
+                let uncovers = COMPUTED.lines.get(enemy_king_square, square) & sliders & !bitboard;
+
+                if uncovers.is_some() || matches!(r#type, PieceType::King | PieceType::Pawn) {
+                    verify = true;
+                } else {
+                    let occupancy = occupancy ^ square;
+                    attacks &= COMPUTED
+                        .attacks
+                        .get(color, r#type, enemy_king_square, occupancy);
                 }
             }
 
             for _square in attacks {
                 let _rank = _square >> 3;
-                let _file = _square & 7;
 
                 let mut flag = MoveFlag::None;
 
@@ -211,23 +259,54 @@ impl Board<'_> {
                     }
 
                     if _rank == 0 || _rank == 7 {
-                        moves.push(Move::new(square, _square, MoveFlag::PromoteQueen));
-                        moves.push(Move::new(square, _square, MoveFlag::PromoteRook));
-                        moves.push(Move::new(square, _square, MoveFlag::PromoteBishop));
-                        moves.push(Move::new(square, _square, MoveFlag::PromoteKnight));
+                        self.add_promotions(gen_type, moves, square, _square);
                         continue;
                     }
                 }
 
-                if r#type == PieceType::King && square.abs_diff(_square) == 2 {
-                    flag = MoveFlag::Castle;
+                let r#move = Move::new(square, _square, flag);
+
+                if !verify || self.gives_check(r#move) {
+                    moves.push(r#move);
                 }
+            }
+        }
+    }
 
-                moves.push(Move::new(square, _square, flag));
+    // Which promotions a GenType keeps depends on the piece, since promoting to a queen is noisy
+    // whether or not it captures
+    fn add_promotions(&self, gen_type: GenType, moves: &mut MoveList, start: u8, end: u8) {
+        let capture = self.squares[end as usize].is_some();
+
+        for flag in [
+            MoveFlag::PromoteQueen,
+            MoveFlag::PromoteRook,
+            MoveFlag::PromoteBishop,
+            MoveFlag::PromoteKnight,
+        ] {
+            let noisy = capture || flag == MoveFlag::PromoteQueen;
+            let r#move = Move::new(start, end, flag);
+
+            let keep = match gen_type {
+                GenType::All | GenType::Evasions => true,
+                GenType::Captures => noisy,
+                GenType::Quiets => !noisy,
+                GenType::QuietChecks => !noisy && self.gives_check(r#move),
+            };
+
+            if keep {
+                moves.push(r#move);
             }
         }
+    }
 
-        moves
+    // Castling moves land on a friendly rook, which isn't a capture
+    pub fn is_noisy(&self, r#move: Move) -> bool {
+        match r#move.get_flag() {
+            MoveFlag::EnPassant | MoveFlag::PromoteQueen => true,
+            MoveFlag::Castle => false,
+            _ => self.squares[r#move.get_end() as usize].is_some(),
+        }
     }
 }
 
@@ -240,9 +319,7 @@ mod tests {
 
         #[test]
         fn double_check() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("4k3/8/8/8/1b6/4r3/8/4K3 w - - 0 1", &computed);
-            board.check_state[PieceColor::White] = CheckState::Double;
+            let board = Board::from_fen("4k3/8/8/8/1b6/4r3/8/4K3 w - - 0 1");
 
             assert_eq!(
                 board.calculate_moves(),
@@ -256,15 +333,27 @@ mod tests {
 
         #[test]
         fn double_check_forced() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("4k3/8/1b6/4r3/8/4K3/r7/4n3 w - - 0 1", &computed);
-            board.check_state[PieceColor::White] = CheckState::Double;
+            let board = Board::from_fen("4k3/8/1b6/4r3/8/4K3/r7/4n3 w - - 0 1");
 
             assert_eq!(
                 board.calculate_moves(),
                 vec![Move::new(square!(E3), square!(F4), MoveFlag::None)]
             );
         }
+
+        #[test]
+        fn discovered_double_check() {
+            let mut board = Board::from_fen("4k3/4r3/8/8/8/4n3/8/4K3 b - - 0 1");
+            board.make_move(Move::new(square!(E3), square!(C2), MoveFlag::None));
+
+            assert!(board.get_check_state() == CheckState::Double);
+            assert!(
+                board
+                    .calculate_moves()
+                    .iter()
+                    .all(|m| m.get_start() == square!(E1))
+            );
+        }
     }
 
     mod enpassant {
@@ -272,8 +361,7 @@ mod tests {
 
         #[test]
         fn allowed() {
-            let computed = Computed::new();
-            let board = Board::from_fen("4k3/8/8/3pP3/4K3/8/8/8 w - d6 0 1", &computed);
+            let board = Board::from_fen("4k3/8/8/3pP3/4K3/8/8/8 w - d6 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -284,8 +372,7 @@ mod tests {
 
         #[test]
         fn disallowed_when_orthogonal_pinned() {
-            let computed = Computed::new();
-            let board = Board::from_fen("4k3/8/8/2rpPK2/8/8/8/8 w - d6 0 1", &computed);
+            let board = Board::from_fen("4k3/8/8/2rpPK2/8/8/8/8 w - d6 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -298,8 +385,7 @@ mod tests {
 
         #[test]
         fn disallowed_when_diagonal_pinned() {
-            let computed = Computed::new();
-            let board = Board::from_fen("4k3/8/2b5/3pP3/4K3/8/8/8 w - - 0 1", &computed);
+            let board = Board::from_fen("4k3/8/2b5/3pP3/4K3/8/8/8 w - d6 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -310,10 +396,20 @@ mod tests {
             );
         }
 
+        #[test]
+        fn allowed_when_capturing_along_pin() {
+            let board = Board::from_fen("8/8/7k/8/4Pp2/8/3Q4/4K3 b - e3 0 1");
+            let moves = board.calculate_moves();
+
+            assert_eq!(
+                moves.iter().find(|m| m.get_flag() == MoveFlag::EnPassant),
+                Some(&Move::new(square!(F4), square!(E3), MoveFlag::EnPassant))
+            );
+        }
+
         #[test]
         fn disallowed_when_both_pinned() {
-            let computed = Computed::new();
-            let board = Board::from_fen("8/6bb/8/8/R1pP2k1/4P3/P7/K7 b - d3 0 1", &computed);
+            let board = Board::from_fen("8/6bb/8/8/R1pP2k1/4P3/P7/K7 b - d3 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -330,8 +426,7 @@ mod tests {
 
         #[test]
         fn allowed() {
-            let computed = Computed::new();
-            let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", &computed);
+            let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -345,8 +440,7 @@ mod tests {
 
         #[test]
         fn allowed_when_file_b_attacked() {
-            let computed = Computed::new();
-            let board = Board::from_fen("4k3/8/8/8/4b3/8/8/R3K2R w KQ - 0 1", &computed);
+            let board = Board::from_fen("4k3/8/8/8/4b3/8/8/R3K2R w KQ - 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -360,9 +454,7 @@ mod tests {
 
         #[test]
         fn disallowed_when_king_checked() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("4k3/8/8/8/8/2b5/8/R3K2R w KQ - 0 1", &computed);
-            board.check_state[PieceColor::White] = CheckState::Single(u8::from(square!(C3)));
+            let board = Board::from_fen("4k3/8/8/8/8/2b5/8/R3K2R w KQ - 0 1");
 
             let moves = board.calculate_moves();
 
@@ -377,8 +469,7 @@ mod tests {
 
         #[test]
         fn disallowed_when_target_square_checked() {
-            let computed = Computed::new();
-            let board = Board::from_fen("4k3/8/8/8/8/4b3/8/R3K2R w KQ - 0 1", &computed);
+            let board = Board::from_fen("4k3/8/8/8/8/4b3/8/R3K2R w KQ - 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -389,7 +480,36 @@ mod tests {
                 0
             );
 
-            let board = Board::from_fen("4k3/8/8/8/8/8/4b3/R3K2R w KQ - 0 1", &computed);
+            let board = Board::from_fen("4k3/8/8/8/8/8/4b3/R3K2R w KQ - 0 1");
+            let moves = board.calculate_moves();
+
+            assert_eq!(
+                moves
+                    .iter()
+                    .filter(|m| m.get_flag() == MoveFlag::Castle)
+                    .count(),
+                0
+            );
+        }
+
+        #[test]
+        fn chess960() {
+            let board = Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1");
+            let mut castles = board
+                .calculate_moves()
+                .into_iter()
+                .filter(|m| m.get_flag() == MoveFlag::Castle)
+                .map(|m| m.get_end())
+                .collect::<Vec<_>>();
+            castles.sort();
+
+            assert_eq!(castles, vec![square!(B1), square!(H1)]);
+        }
+
+        #[test]
+        fn chess960_disallowed_when_rook_hides_attack() {
+            // Once the b1 rook leaves, the a1 rook attacks the king on c1
+            let board = Board::from_fen("4k3/8/8/8/8/8/8/rRK5 w B - 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -407,8 +527,7 @@ mod tests {
 
         #[test]
         fn white() {
-            let computed = Computed::new();
-            let board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", &computed);
+            let board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -422,8 +541,7 @@ mod tests {
 
         #[test]
         fn black() {
-            let computed = Computed::new();
-            let board = Board::from_fen("4k3/8/8/8/8/8/p7/4K3 b - - 0 1", &computed);
+            let board = Board::from_fen("4k3/8/8/8/8/8/p7/4K3 b - - 0 1");
             let moves = board.calculate_moves();
 
             assert_eq!(
@@ -435,4 +553,89 @@ mod tests {
             );
         }
     }
+
+    mod gen_type {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn generate(board: &Board, gen_type: GenType) -> Vec<Move> {
+            let mut moves = MoveList::new();
+            board.generate_moves(gen_type, &mut moves);
+            moves.to_vec()
+        }
+
+        proptest! {
+            #[test]
+            fn captures_and_quiets_partition_all(
+                mut board in perft_board(),
+                choices in choices(40),
+            ) {
+                playout(&mut board, &choices, |board, all, _| {
+                    let captures = generate(board, GenType::Captures);
+                    let quiets = generate(board, GenType::Quiets);
+
+                    prop_assert_eq!(captures.len() + quiets.len(), all.len(), "{}", board.to_fen());
+                    let partitioned = all.iter().all(|r#move| {
+                        captures.contains(r#move) != quiets.contains(r#move)
+                            && board.is_noisy(*r#move) == captures.contains(r#move)
+                    });
+                    prop_assert!(partitioned, "{}", board.to_fen());
+                    Ok(())
+                })?;
+            }
+
+            #[test]
+            fn evasions_only_in_check(mut board in perft_board(), choices in choices(40)) {
+                playout(&mut board, &choices, |board, all, _| {
+                    let evasions = generate(board, GenType::Evasions);
+
+                    if board.is_in_check() {
+                        prop_assert_eq!(evasions, all.to_vec(), "{}", board.to_fen());
+                    } else {
+                        prop_assert_eq!(evasions, vec![], "{}", board.to_fen());
+                    }
+                    Ok(())
+                })?;
+            }
+
+            #[test]
+            fn quiet_checks(mut board in perft_board(), choices in choices(40)) {
+                playout(&mut board, &choices, |board, all, _| {
+                    let mut expected = vec![];
+
+                    for &r#move in all {
+                        let gives_check = board.gives_check(r#move);
+
+                        board.make_move(r#move);
+                        let is_in_check = board.is_in_check();
+                        board.undo_move(r#move);
+
+                        let fen = board.to_fen();
+                        prop_assert_eq!(gives_check, is_in_check, "{} {:?}", fen, r#move);
+
+                        if gives_check && !board.is_noisy(r#move) {
+                            expected.push(r#move);
+                        }
+                    }
+
+                    prop_assert_eq!(generate(board, GenType::QuietChecks), expected);
+                    Ok(())
+                })?;
+            }
+        }
+
+        #[test]
+        fn castling_rook_gives_check() {
+            let board = Board::from_fen("5k2/8/8/8/8/8/8/4K2R w K - 0 1");
+
+            assert_eq!(
+                generate(&board, GenType::QuietChecks),
+                vec![
+                    Move::new(square!(E1), square!(H1), MoveFlag::Castle),
+                    Move::new(square!(H1), square!(F1), MoveFlag::None),
+                    Move::new(square!(H1), square!(H8), MoveFlag::None),
+                ]
+            );
+        }
+    }
 }
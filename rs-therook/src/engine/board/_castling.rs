@@ -0,0 +1,45 @@
+use super::*;
+
+// https://www.chessprogramming.org/Castling
+// https://www.chessprogramming.org/Chess960#Castling
+impl Board {
+    pub fn get_castling_rook(&self, castle: Piece) -> Option<u8> {
+        let file = self.get_state().castling[castle]?;
+        Some(castle.get_color().get_home_row() * 8 + file)
+    }
+
+    // The king and rook may start anywhere on the back rank in Chess960, so instead of fixed squares
+    // everything both pieces travel over has to be empty and the king may not pass through a square
+    // the enemy attacks
+    pub(crate) fn can_castle(&self, king_square: u8, rook_square: u8) -> bool {
+        let color = self.turn;
+        let enemy = color.opposite();
+
+        let r#move = Move::new(king_square, rook_square, MoveFlag::Castle);
+        let (king_end, rook_end) = r#move.get_castling_squares();
+
+        // The castling pieces never block each other
+        let occupancy = (self.colors[color] | self.colors[enemy]) ^ king_square ^ rook_square;
+
+        let betweens = &COMPUTED.betweens;
+        let king_path = betweens.get(king_square, king_end) | king_end;
+        let rook_path = betweens.get(rook_square, rook_end) | rook_end;
+
+        // A rook or queen behind the castling rook only attacks the king once the rook has moved away
+        let rank_attackers =
+            COMPUTED
+                .attacks
+                .get(color, PieceType::Rook, king_end, occupancy | rook_end)
+                & (self.pieces[enemy | PieceType::Rook] | self.pieces[enemy | PieceType::Queen]);
+
+        // Each square the king passes over is looked up on its own. Sliders seeing through the king
+        // would already be giving check, so it can stay where it is
+        let full_occupancy = self.colors[color] | self.colors[enemy];
+        let is_attacked = |square: u8| self.is_attacked(square, full_occupancy, enemy);
+
+        self.get_checkers().is_none()
+            && (occupancy & (king_path | rook_path)).is_none()
+            && rank_attackers.is_none()
+            && !king_path.into_iter().any(is_attacked)
+    }
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+// https://www.chessprogramming.org/Checks_and_Pinned_Pieces_(Bitboards)#Checks
+impl Board {
+    // Whether the move would leave the enemy king in check, without making it. Either the moved
+    // piece attacks the king from where it lands, or it uncovers a slider standing behind it
+    pub fn gives_check(&self, r#move: Move) -> bool {
+        let color = self.turn;
+        let enemy = color.opposite();
+
+        let Ok(king_square) = u8::try_from(self.pieces[enemy | PieceType::King]) else {
+            return false;
+        };
+
+        let start_square = r#move.get_start();
+        let end_square = r#move.get_end();
+        let flag = r#move.get_flag();
+
+        let piece = self.squares[start_square as usize].unwrap();
+        let mut occupancy = (self.colors[color] | self.colors[enemy]) ^ start_square;
+        let mut unmoved = self.colors[color] ^ start_square;
+
+        // Only the rook of a castling move can give check, since kings never attack each other
+        let (r#type, square) = if flag == MoveFlag::Castle {
+            let (king_end, rook_end) = r#move.get_castling_squares();
+            occupancy ^= end_square;
+            occupancy |= Bitboard::from(king_end) | rook_end;
+            unmoved ^= end_square;
+
+            (PieceType::Rook, rook_end)
+        } else {
+            occupancy |= end_square;
+
+            if flag == MoveFlag::EnPassant {
+                occupancy ^= (start_square & 56) + (end_square & 7);
+            }
+
+            let r#type = r#move.get_promote_piece_type().unwrap_or(piece.get_type());
+
+            (r#type, end_square)
+        };
+
+        let direct = COMPUTED.attacks.get(color, r#type, square, occupancy);
+        if (direct & king_square).is_some() {
+            return true;
+        }
+
+        let orthogonal = (self.pieces[color | PieceType::Rook]
+            | self.pieces[color | PieceType::Queen])
+            & unmoved;
+        let diagonal = (self.pieces[color | PieceType::Bishop]
+            | self.pieces[color | PieceType::Queen])
+            & unmoved;
+
+        let orthogonal_attackers =
+            COMPUTED
+                .attacks
+                .get(color, PieceType::Rook, king_square, occupancy)
+                & orthogonal;
+        let diagonal_attackers =
+            COMPUTED
+                .attacks
+                .get(color, PieceType::Bishop, king_square, occupancy)
+                & diagonal;
+
+        (orthogonal_attackers | diagonal_attackers).is_some()
+    }
+}
@@ -43,14 +43,14 @@ impl<T> std::ops::IndexMut<Piece> for [T; 12] {
     }
 }
 
-impl std::ops::Index<Piece> for [bool; 4] {
-    type Output = bool;
+impl<T> std::ops::Index<Piece> for [T; 4] {
+    type Output = T;
     fn index(&self, index: Piece) -> &Self::Output {
         &self[index.get_castle_index()]
     }
 }
 
-impl std::ops::IndexMut<Piece> for [bool; 4] {
+impl<T> std::ops::IndexMut<Piece> for [T; 4] {
     fn index_mut(&mut self, index: Piece) -> &mut Self::Output {
         &mut self[index.get_castle_index()]
     }
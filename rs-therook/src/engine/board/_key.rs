@@ -0,0 +1,145 @@
+use super::*;
+
+impl Board {
+    // https://www.chessprogramming.org/Zobrist_Hashing
+    pub fn calculate_key(&self) -> u64 {
+        let state = self.get_state();
+        let mut key = 0;
+
+        for square in self.colors[PieceColor::White] | self.colors[PieceColor::Black] {
+            key ^= Zobrist::piece(self.squares[square as usize].unwrap(), square);
+        }
+
+        for (index, castling) in state.castling.iter().enumerate() {
+            if castling.is_some() {
+                key ^= Zobrist::castling(index);
+            }
+        }
+
+        if self.is_enpassant_hashed(state.enpassant, self.turn) {
+            key ^= Zobrist::enpassant(u8::try_from(state.enpassant).unwrap() & 7);
+        }
+
+        if self.turn == PieceColor::White {
+            key ^= Zobrist::turn();
+        }
+
+        key
+    }
+
+    // Polyglot only hashes the enpassant file if a pawn of the side to move stands ready to capture
+    pub(crate) fn is_enpassant_hashed(&self, enpassant: Bitboard, color: PieceColor) -> bool {
+        // Checked first since converting an empty Bitboard builds an error message on the heap
+        if enpassant.is_none() {
+            return false;
+        }
+
+        let enpassant_square = u8::try_from(enpassant).unwrap();
+
+        let capturers = COMPUTED.attacks.get(
+            color.opposite(),
+            PieceType::Pawn,
+            enpassant_square,
+            Bitboard::new(),
+        );
+
+        (capturers & self.pieces[color | PieceType::Pawn]).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // http://hgm.nubati.net/book_format.html
+    #[test]
+    fn polyglot() {
+        let mut board = Board::initial();
+        assert_eq!(board.get_key(), 0x463b96181691fc9c);
+
+        let moves = [
+            (
+                square!(E2),
+                square!(E4),
+                MoveFlag::PawnDash,
+                0x823c9b50fd114196,
+            ),
+            (
+                square!(D7),
+                square!(D5),
+                MoveFlag::PawnDash,
+                0x0756b94461c50fb0,
+            ),
+            (square!(E4), square!(E5), MoveFlag::None, 0x662fafb965db29d4),
+            (
+                square!(F7),
+                square!(F5),
+                MoveFlag::PawnDash,
+                0x22a48b5a8e47ff78,
+            ),
+            (square!(E1), square!(E2), MoveFlag::None, 0x652a607ca3f242c1),
+            (square!(E8), square!(F7), MoveFlag::None, 0x00fdd303c946bdd9),
+        ];
+
+        for (start, end, flag, key) in moves {
+            board.make_move(Move::new(start, end, flag));
+            assert_eq!(board.get_key(), key, "{board:?}");
+        }
+
+        let mut board = Board::initial();
+
+        let moves = [
+            (square!(A2), square!(A4), MoveFlag::PawnDash),
+            (square!(B7), square!(B5), MoveFlag::PawnDash),
+            (square!(H2), square!(H4), MoveFlag::PawnDash),
+            (square!(B5), square!(B4), MoveFlag::None),
+            (square!(C2), square!(C4), MoveFlag::PawnDash),
+        ];
+
+        for (start, end, flag) in moves {
+            board.make_move(Move::new(start, end, flag));
+        }
+        assert_eq!(board.get_key(), 0x3c8123ea7b067637);
+
+        board.make_move(Move::new(square!(B4), square!(C3), MoveFlag::EnPassant));
+        board.make_move(Move::new(square!(A1), square!(A3), MoveFlag::None));
+        assert_eq!(board.get_key(), 0x5c3f9b829b279560);
+    }
+
+    #[test]
+    fn restored_after_undo() {
+        let mut board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        let key = board.get_key();
+
+        for r#move in board.calculate_moves() {
+            board.make_move(r#move);
+            assert_eq!(board.get_key(), board.calculate_key());
+
+            for reply in board.calculate_moves() {
+                board.make_move(reply);
+                assert_eq!(board.get_key(), board.calculate_key());
+                board.undo_move(reply);
+            }
+
+            board.undo_move(r#move);
+            assert_eq!(board.get_key(), key);
+        }
+    }
+
+    #[test]
+    fn transpositions_match() {
+        let mut a = Board::initial();
+        a.make_move(Move::new(square!(G1), square!(F3), MoveFlag::None));
+        a.make_move(Move::new(square!(G8), square!(F6), MoveFlag::None));
+        a.make_move(Move::new(square!(B1), square!(C3), MoveFlag::None));
+
+        let mut b = Board::initial();
+        b.make_move(Move::new(square!(B1), square!(C3), MoveFlag::None));
+        b.make_move(Move::new(square!(G8), square!(F6), MoveFlag::None));
+        b.make_move(Move::new(square!(G1), square!(F3), MoveFlag::None));
+
+        assert_eq!(a.get_key(), b.get_key());
+        assert_ne!(a.get_key(), Board::initial().get_key());
+    }
+}
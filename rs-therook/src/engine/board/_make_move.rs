@@ -1,6 +1,6 @@
 use super::*;
 
-impl Board<'_> {
+impl Board {
     pub fn make_move(&mut self, r#move: Move) {
         let start_square = r#move.get_start();
         let end_square = r#move.get_end();
@@ -17,97 +17,67 @@ impl Board<'_> {
         let color = piece.get_color();
         let enemy = color.opposite();
 
-        let mut state = self.get_state().clone();
+        let mut state = *self.get_state();
 
+        // Castling moves land on the castling rook, which is not captured
         state.captured = if is_enpassant {
             Some(color.opposite() | PieceType::Pawn)
+        } else if is_castle {
+            None
         } else {
             self.squares[end_square as usize]
         };
-        let captured_type = state.captured.map(|p| p.get_type());
-
-        self.clear_square(start_square, piece);
-
-        // Remove the captured tile, the piece on the end square or enpassant square
-        if let Some(captured) = state.captured {
-            self.clear_square(
-                if is_enpassant {
-                    (start_square & 56) + (end_square & 7)
-                } else {
-                    end_square
-                },
-                captured,
-            );
-        }
 
-        // Set piece back on the board
-        if let Some(r#type) = promotion_piece_type {
-            self.set_square(end_square, color | r#type);
-        } else {
-            self.set_square(end_square, piece);
+        // Update enpassant square
+        if self.is_enpassant_hashed(state.enpassant, color) {
+            state.key ^= Zobrist::enpassant(u8::try_from(state.enpassant).unwrap() & 7);
         }
 
-        // Update enpassant square
         if is_pawn_dash {
             if color == PieceColor::White {
                 state.enpassant = Bitboard::from((start_square & 56) + (end_square & 7) + 8)
             } else {
                 state.enpassant = Bitboard::from((start_square & 56) + (end_square & 7) - 8)
             }
+
+            if self.is_enpassant_hashed(state.enpassant, enemy) {
+                state.key ^= Zobrist::enpassant(end_square & 7);
+            }
         } else if state.enpassant.is_some() {
             state.enpassant = Bitboard::new()
         }
 
-        // Castling rights & move castled rook
-        if is_castle {
-            let (from_square, to_square, piece) = match end_square {
-                square!(G1) => (square!(H1), square!(F1), WHITE_ROOK),
-                square!(C1) => (square!(A1), square!(D1), WHITE_ROOK),
-                square!(G8) => (square!(H8), square!(F8), BLACK_ROOK),
-                square!(C8) => (square!(A8), square!(D8), BLACK_ROOK),
-                _ => unreachable!(),
-            };
-
-            self.clear_square(from_square, piece);
-            self.set_square(to_square, piece);
-        }
+        // Castling rights
+        let castling = state.castling;
 
-        if state.castling != [false; 4] {
-            // Check if we still can castle
-            if state.castling[color | PieceType::King] || state.castling[color | PieceType::Queen] {
-                if piece_type == PieceType::King {
-                    state.castling[color | PieceType::King] = false;
-                    state.castling[color | PieceType::Queen] = false;
-                }
+        if state.castling != [None; 4] {
+            if piece_type == PieceType::King {
+                state.castling[color | PieceType::King] = None;
+                state.castling[color | PieceType::Queen] = None;
+            }
 
-                if piece_type == PieceType::Rook && start_square >> 3 == color.get_home_row() {
-                    if start_square & 7 == 0 {
-                        state.castling[color | PieceType::Queen] = false;
-                    }
+            // A rook that moves away or gets captured takes its right along with it
+            for castle in [WHITE_KING, WHITE_QUEEN, BLACK_KING, BLACK_QUEEN] {
+                if let Some(file) = state.castling[castle] {
+                    let rook_square = castle.get_color().get_home_row() * 8 + file;
 
-                    if start_square & 7 == 7 {
-                        state.castling[color | PieceType::King] = false;
+                    if start_square == rook_square || end_square == rook_square {
+                        state.castling[castle] = None;
                     }
                 }
             }
+        }
 
-            // Check if opponent can still castle
-            if state.castling[enemy | PieceType::King] || state.castling[enemy | PieceType::Queen] {
-                if captured_type == Some(PieceType::Rook) && end_square >> 3 == enemy.get_home_row()
-                {
-                    if end_square & 7 == 0 {
-                        state.castling[enemy | PieceType::Queen] = false;
-                    }
-
-                    if end_square & 7 == 7 {
-                        state.castling[enemy | PieceType::King] = false;
-                    }
-                }
+        for (index, (before, after)) in castling.iter().zip(state.castling).enumerate() {
+            if before.is_some() != after.is_some() {
+                state.key ^= Zobrist::castling(index);
             }
         }
 
-        // Increment halfmove & fullmove
-        if piece_type != PieceType::Pawn && state.captured.is_none() {
+        // Pawn moves and captures are irreversible and restart the fifty-move clock
+        if piece_type == PieceType::Pawn || state.captured.is_some() {
+            state.halfmove = 0;
+        } else {
             state.halfmove += 1;
         }
 
@@ -115,14 +85,49 @@ impl Board<'_> {
             state.fullmove += 1;
         }
 
-        for color in PieceColor::ALL {
-            self.update_attacks(color);
-            self.update_pin_lines(color);
-        }
+        state.key ^= Zobrist::turn();
 
+        // Pushed before moving pieces around so that the squares get hashed into the new state
         self.states.push(state);
 
+        let captured = self.get_state().captured;
+
+        if is_castle {
+            let rook = color | PieceType::Rook;
+            let (king_end, rook_end) = r#move.get_castling_squares();
+
+            // Both pieces are lifted first since either may land where the other started
+            self.clear_square(start_square, piece);
+            self.clear_square(end_square, rook);
+            self.set_square(king_end, piece);
+            self.set_square(rook_end, rook);
+        } else {
+            self.clear_square(start_square, piece);
+
+            // Remove the captured tile, the piece on the end square or enpassant square
+            if let Some(captured) = captured {
+                self.clear_square(
+                    if is_enpassant {
+                        (start_square & 56) + (end_square & 7)
+                    } else {
+                        end_square
+                    },
+                    captured,
+                );
+            }
+
+            // Set piece back on the board
+            if let Some(r#type) = promotion_piece_type {
+                self.set_square(end_square, color | r#type);
+            } else {
+                self.set_square(end_square, piece);
+            }
+        }
+
         self.turn = enemy;
+        self.update_checkers_and_pinned();
+
+        debug_assert_eq!(self.get_key(), self.calculate_key());
     }
 }
 
@@ -135,11 +140,8 @@ mod tests {
 
         #[test]
         fn captured_pawn_disappears() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen(
-                "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 1",
-                &computed,
-            );
+            let mut board =
+                Board::from_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 1");
 
             board.make_move(Move::new(square!(E5), square!(D6), MoveFlag::EnPassant));
             assert_eq!(
@@ -150,8 +152,7 @@ mod tests {
 
         #[test]
         fn pawn_dash_sets_enpassant_square() {
-            let computed = Computed::new();
-            let mut board = Board::initial(&computed);
+            let mut board = Board::initial();
 
             board.make_move(Move::new(square!(E2), square!(E4), MoveFlag::PawnDash));
             assert_eq!(
@@ -168,8 +169,7 @@ mod tests {
 
         #[test]
         fn move_after_enpassant_resets_enpassant_square() {
-            let computed = Computed::new();
-            let mut board = Board::initial(&computed);
+            let mut board = Board::initial();
 
             board.make_move(Move::new(square!(E2), square!(E4), MoveFlag::PawnDash));
             assert_eq!(
@@ -190,41 +190,29 @@ mod tests {
 
         #[test]
         fn kingside() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen(
-                "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
-                &computed,
-            );
+            let mut board = Board::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1");
 
-            board.make_move(Move::new(square!(E1), square!(G1), MoveFlag::Castle));
+            board.make_move(Move::new(square!(E1), square!(H1), MoveFlag::Castle));
             assert_eq!(&board, "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R4RK1 b kq - 1 1");
 
-            board.make_move(Move::new(square!(E8), square!(G8), MoveFlag::Castle));
+            board.make_move(Move::new(square!(E8), square!(H8), MoveFlag::Castle));
             assert_eq!(&board, "r4rk1/pppppppp/8/8/8/8/PPPPPPPP/R4RK1 w - - 2 2");
         }
 
         #[test]
         fn queenside() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen(
-                "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
-                &computed,
-            );
+            let mut board = Board::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1");
 
-            board.make_move(Move::new(square!(E1), square!(C1), MoveFlag::Castle));
+            board.make_move(Move::new(square!(E1), square!(A1), MoveFlag::Castle));
             assert_eq!(&board, "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/2KR3R b kq - 1 1");
 
-            board.make_move(Move::new(square!(E8), square!(C8), MoveFlag::Castle));
+            board.make_move(Move::new(square!(E8), square!(A8), MoveFlag::Castle));
             assert_eq!(&board, "2kr3r/pppppppp/8/8/8/8/PPPPPPPP/2KR3R w - - 2 2");
         }
 
         #[test]
         fn moving_rook_loses_rights() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen(
-                "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
-                &computed,
-            );
+            let mut board = Board::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1");
 
             board.make_move(Move::new(square!(H1), square!(G1), MoveFlag::None));
             assert_eq!(&board, "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K1R1 b Qkq - 1 1");
@@ -244,11 +232,7 @@ mod tests {
 
         #[test]
         fn moving_king_loses_rights() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen(
-                "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
-                &computed,
-            );
+            let mut board = Board::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1");
 
             board.make_move(Move::new(square!(E1), square!(D1), MoveFlag::None));
             assert_eq!(&board, "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R2K3R b kq - 1 1");
@@ -259,9 +243,7 @@ mod tests {
 
         #[test]
         fn capturing_rook_revokes_rights() {
-            let computed = Computed::new();
-            let mut board =
-                Board::from_fen("r3k2r/8/8/3BB3/3bb3/8/8/R3K2R w KQkq - 0 1", &computed);
+            let mut board = Board::from_fen("r3k2r/8/8/3BB3/3bb3/8/8/R3K2R w KQkq - 0 1");
 
             board.make_move(Move::new(square!(E5), square!(H8), MoveFlag::None));
             assert_eq!(&board, "r3k2B/8/8/3B4/3bb3/8/8/R3K2R b KQq - 0 1");
@@ -277,13 +259,54 @@ mod tests {
         }
     }
 
+    mod counters {
+        use super::*;
+
+        #[test]
+        fn quiet_move_increments_halfmove() {
+            let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 10 40");
+
+            board.make_move(Move::new(square!(A1), square!(A5), MoveFlag::None));
+            assert_eq!(&board, "4k3/8/8/R7/8/8/8/4K3 b - - 11 40");
+
+            board.make_move(Move::new(square!(E8), square!(D7), MoveFlag::None));
+            assert_eq!(&board, "8/3k4/8/R7/8/8/8/4K3 w - - 12 41");
+        }
+
+        #[test]
+        fn pawn_move_resets_halfmove() {
+            let mut board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 37 60");
+
+            board.make_move(Move::new(square!(E2), square!(E3), MoveFlag::None));
+            assert_eq!(&board, "4k3/8/8/8/8/4P3/8/4K3 b - - 0 60");
+        }
+
+        #[test]
+        fn capture_resets_halfmove() {
+            let mut board = Board::from_fen("4k3/8/8/r7/8/8/8/R3K3 b - - 37 60");
+
+            board.make_move(Move::new(square!(A5), square!(A1), MoveFlag::None));
+            assert_eq!(&board, "4k3/8/8/8/8/8/8/r3K3 w - - 0 61");
+        }
+
+        #[test]
+        fn counters_beyond_u8() {
+            let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 b - - 299 1000");
+
+            board.make_move(Move::new(square!(E8), square!(D7), MoveFlag::None));
+            assert_eq!(&board, "8/3k4/8/8/8/8/8/R3K3 w - - 300 1001");
+
+            board.undo_move(Move::new(square!(E8), square!(D7), MoveFlag::None));
+            assert_eq!(&board, "4k3/8/8/8/8/8/8/R3K3 b - - 299 1000");
+        }
+    }
+
     mod promote {
         use super::*;
 
         #[test]
         fn queen() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", &computed);
+            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
 
             board.make_move(Move::new(square!(A7), square!(A8), MoveFlag::PromoteQueen));
             assert_eq!(&board, "Q3k3/8/8/8/8/8/8/4K3 b - - 0 1");
@@ -291,8 +314,7 @@ mod tests {
 
         #[test]
         fn rook() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", &computed);
+            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
 
             board.make_move(Move::new(square!(A7), square!(A8), MoveFlag::PromoteRook));
             assert_eq!(&board, "R3k3/8/8/8/8/8/8/4K3 b - - 0 1");
@@ -300,8 +322,7 @@ mod tests {
 
         #[test]
         fn bishop() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", &computed);
+            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
 
             board.make_move(Move::new(square!(A7), square!(A8), MoveFlag::PromoteBishop));
             assert_eq!(&board, "B3k3/8/8/8/8/8/8/4K3 b - - 0 1");
@@ -309,8 +330,7 @@ mod tests {
 
         #[test]
         fn knight() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", &computed);
+            let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
 
             board.make_move(Move::new(square!(A7), square!(A8), MoveFlag::PromoteKnight));
             assert_eq!(&board, "N3k3/8/8/8/8/8/8/4K3 b - - 0 1");
@@ -318,8 +338,7 @@ mod tests {
 
         #[test]
         fn queen_with_capture() {
-            let computed = Computed::new();
-            let mut board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1", &computed);
+            let mut board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1");
 
             board.make_move(Move::new(square!(A7), square!(B8), MoveFlag::PromoteQueen));
             assert_eq!(&board, "1Q2k3/8/8/8/8/8/8/4K3 b - - 0 1");
@@ -0,0 +1,182 @@
+use super::*;
+
+impl Board {
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.calculate_moves().is_empty() {
+            return Some(if self.is_in_check() {
+                Outcome::Checkmate(self.turn.opposite())
+            } else {
+                Outcome::Stalemate
+            });
+        }
+
+        if self.is_insufficient_material() {
+            return Some(Outcome::InsufficientMaterial);
+        }
+
+        let repetitions = self.get_repetitions();
+        let halfmove = self.get_state().halfmove;
+
+        if repetitions >= 5 {
+            Some(Outcome::FivefoldRepetition)
+        } else if halfmove >= 150 {
+            Some(Outcome::SeventyFiveMoveRule)
+        } else if repetitions >= 3 {
+            Some(Outcome::ThreefoldRepetition)
+        } else if halfmove >= 100 {
+            Some(Outcome::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    // Number of times the current position has occurred, including the current occurrence.
+    // Positions can only repeat with the same side to move, and never across a capture or pawn move
+    // https://www.chessprogramming.org/Repetitions
+    pub fn get_repetitions(&self) -> usize {
+        let key = self.get_key();
+        let reversible = self.get_state().halfmove as usize;
+
+        self.states
+            .iter()
+            .rev()
+            .take(reversible + 1)
+            .step_by(2)
+            .filter(|state| state.key == key)
+            .count()
+    }
+
+    // Neither side can checkmate with any sequence of legal moves: lone kings, a single minor piece,
+    // or any number of bishops that all stand on the same colour
+    // https://www.chessprogramming.org/Draw_Evaluation#Insufficient_Material
+    pub fn is_insufficient_material(&self) -> bool {
+        let heavy = PieceColor::ALL.iter().any(|&color| {
+            [PieceType::Queen, PieceType::Rook, PieceType::Pawn]
+                .iter()
+                .any(|&r#type| self.pieces[color | r#type].is_some())
+        });
+
+        if heavy {
+            return false;
+        }
+
+        let knights = self.pieces[WHITE_KNIGHT] | self.pieces[BLACK_KNIGHT];
+        let bishops = self.pieces[WHITE_BISHOP] | self.pieces[BLACK_BISHOP];
+        let minors = (knights | bishops).count();
+
+        minors <= 1
+            || (knights.is_none()
+                && ((bishops & LIGHT_SQUARES).is_none() || (bishops & DARK_SQUARES).is_none()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(board: &mut Board, moves: &[(u8, u8)]) {
+        for &(start, end) in moves {
+            board.make_move(Move::new(start, end, MoveFlag::None));
+        }
+    }
+
+    const SHUFFLE: [(u8, u8); 4] = [
+        (square!(G1), square!(F3)),
+        (square!(G8), square!(F6)),
+        (square!(F3), square!(G1)),
+        (square!(F6), square!(G8)),
+    ];
+
+    #[test]
+    fn ongoing() {
+        let board = Board::initial();
+
+        assert_eq!(board.outcome(), None);
+        assert_eq!(board.get_repetitions(), 1);
+    }
+
+    #[test]
+    fn checkmate() {
+        let board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1");
+
+        let outcome = board.outcome();
+        assert_eq!(outcome, Some(Outcome::Checkmate(PieceColor::White)));
+        assert_eq!(outcome.unwrap().get_winner(), Some(PieceColor::White));
+    }
+
+    #[test]
+    fn stalemate() {
+        let board = Board::from_fen("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1");
+
+        assert_eq!(board.outcome(), Some(Outcome::Stalemate));
+        assert!(Outcome::Stalemate.is_draw());
+    }
+
+    #[test]
+    fn checkmate_beats_fifty_move_rule() {
+        let board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 100 80");
+
+        assert_eq!(board.outcome(), Some(Outcome::Checkmate(PieceColor::White)));
+    }
+
+    #[test]
+    fn threefold_repetition() {
+        let mut board = Board::initial();
+
+        play(&mut board, &SHUFFLE);
+        assert_eq!(board.get_repetitions(), 2);
+        assert_eq!(board.outcome(), None);
+
+        play(&mut board, &SHUFFLE);
+        assert_eq!(board.get_repetitions(), 3);
+        assert_eq!(board.outcome(), Some(Outcome::ThreefoldRepetition));
+        assert!(Outcome::ThreefoldRepetition.is_claimable());
+
+        board.undo_move(Move::new(square!(F6), square!(G8), MoveFlag::None));
+        assert_eq!(board.outcome(), None);
+    }
+
+    #[test]
+    fn fivefold_repetition() {
+        let mut board = Board::initial();
+
+        for _ in 0..4 {
+            play(&mut board, &SHUFFLE);
+        }
+
+        assert_eq!(board.get_repetitions(), 5);
+        assert_eq!(board.outcome(), Some(Outcome::FivefoldRepetition));
+        assert!(!Outcome::FivefoldRepetition.is_claimable());
+    }
+
+    #[test]
+    fn fifty_move_rule() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80");
+        assert_eq!(board.outcome(), None);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80");
+        assert_eq!(board.outcome(), Some(Outcome::FiftyMoveRule));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 150 105");
+        assert_eq!(board.outcome(), Some(Outcome::SeventyFiveMoveRule));
+    }
+
+    #[test]
+    fn insufficient_material() {
+        let insufficient = |fen| Board::from_fen(fen).is_insufficient_material();
+
+        assert!(insufficient("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+        assert!(insufficient("4k3/8/8/8/8/8/8/2N1K3 w - - 0 1"));
+        assert!(insufficient("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+        assert!(insufficient("2b1k3/8/8/8/8/8/8/3BK3 w - - 0 1"));
+
+        assert!(!insufficient("3bk3/8/8/8/8/8/8/3BK3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1"));
+        assert!(!insufficient("2n1k3/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/3PK3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/3RK3 w - - 0 1"));
+
+        let board = Board::from_fen("2b1k3/8/8/8/8/8/8/3BK3 w - - 0 1");
+        assert_eq!(board.outcome(), Some(Outcome::InsufficientMaterial));
+    }
+}
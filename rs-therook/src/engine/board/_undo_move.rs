@@ -1,6 +1,6 @@
 use super::*;
 
-impl Board<'_> {
+impl Board {
     pub fn undo_move(&mut self, r#move: Move) {
         let start_square = r#move.get_start();
         let end_square = r#move.get_end();
@@ -10,52 +10,53 @@ impl Board<'_> {
         let is_castle = flag == MoveFlag::Castle;
         let is_promote = r#move.get_promote_piece_type().is_some();
 
-        let piece = self.squares[end_square as usize].unwrap();
-        let color = piece.get_color();
+        let captured = self.get_state().captured;
 
-        let state = self
-            .states
-            .pop()
-            .unwrap_or_else(|| panic!("No board state..."));
+        let color = if is_castle {
+            let (king_end, rook_end) = r#move.get_castling_squares();
+            let king = self.squares[king_end as usize].unwrap();
+            let rook = self.squares[rook_end as usize].unwrap();
 
-        if is_castle {
-            let (from_square, to_square, piece) = match end_square {
-                square!(G1) => (square!(F1), square!(H1), WHITE_ROOK),
-                square!(C1) => (square!(D1), square!(A1), WHITE_ROOK),
-                square!(G8) => (square!(F8), square!(H8), BLACK_ROOK),
-                square!(C8) => (square!(D8), square!(A8), BLACK_ROOK),
-                _ => unreachable!(),
-            };
-
-            self.clear_square(from_square, piece);
-            self.set_square(to_square, piece);
-        }
+            self.clear_square(king_end, king);
+            self.clear_square(rook_end, rook);
+            self.set_square(start_square, king);
+            self.set_square(end_square, rook);
 
-        if is_promote {
-            self.set_square(start_square, color | PieceType::Pawn);
+            king.get_color()
         } else {
-            self.set_square(start_square, piece);
-        }
-
-        self.clear_square(end_square, piece);
-
-        if let Some(captured) = state.captured {
-            self.set_square(
-                if is_enpassant {
-                    (start_square & 56) + (end_square & 7)
-                } else {
-                    end_square
-                },
-                captured,
-            );
-        }
-
-        for color in PieceColor::ALL {
-            self.update_attacks(color);
-            self.update_pin_lines(color);
-        }
+            let piece = self.squares[end_square as usize].unwrap();
+
+            if is_promote {
+                self.set_square(start_square, piece.get_color() | PieceType::Pawn);
+            } else {
+                self.set_square(start_square, piece);
+            }
+
+            self.clear_square(end_square, piece);
+
+            if let Some(captured) = captured {
+                self.set_square(
+                    if is_enpassant {
+                        (start_square & 56) + (end_square & 7)
+                    } else {
+                        end_square
+                    },
+                    captured,
+                );
+            }
+
+            piece.get_color()
+        };
+
+        // Popped after moving pieces back so the squares only get hashed into the discarded state,
+        // and the checkers and pinned pieces saved before the move come back with it
+        self.states
+            .pop()
+            .unwrap_or_else(|| panic!("No board state..."));
 
         self.turn = color;
+
+        debug_assert_eq!(self.get_key(), self.calculate_key());
     }
 }
 
@@ -69,8 +70,7 @@ mod tests {
         #[test]
         fn resets() {
             let fen = "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let r#move = Move::new(square!(E5), square!(D6), MoveFlag::EnPassant);
 
             board.make_move(r#move);
@@ -87,10 +87,9 @@ mod tests {
         #[test]
         fn kingside() {
             let fen = "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
-            let white_move = Move::new(square!(E1), square!(G1), MoveFlag::Castle);
-            let black_move = Move::new(square!(E8), square!(G8), MoveFlag::Castle);
+            let mut board = Board::from_fen(fen);
+            let white_move = Move::new(square!(E1), square!(H1), MoveFlag::Castle);
+            let black_move = Move::new(square!(E8), square!(H8), MoveFlag::Castle);
 
             board.make_move(white_move);
             board.make_move(black_move);
@@ -104,10 +103,9 @@ mod tests {
         #[test]
         fn queenside() {
             let fen = "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
-            let white_move = Move::new(square!(E1), square!(C1), MoveFlag::Castle);
-            let black_move = Move::new(square!(E8), square!(C8), MoveFlag::Castle);
+            let mut board = Board::from_fen(fen);
+            let white_move = Move::new(square!(E1), square!(A1), MoveFlag::Castle);
+            let black_move = Move::new(square!(E8), square!(A8), MoveFlag::Castle);
 
             board.make_move(white_move);
             board.make_move(black_move);
@@ -118,11 +116,27 @@ mod tests {
             assert_eq!(&board, fen);
         }
 
+        #[test]
+        fn chess960_swapping_king_and_rook() {
+            let fen = "5kr1/8/8/8/8/8/8/RK6 w Qk - 0 1";
+            let mut board = Board::from_fen(fen);
+            let white_move = Move::new(square!(B1), square!(A1), MoveFlag::Castle);
+            let black_move = Move::new(square!(F8), square!(G8), MoveFlag::Castle);
+
+            board.make_move(white_move);
+            board.make_move(black_move);
+            assert_eq!(&board, "5rk1/8/8/8/8/8/8/2KR4 w - - 2 2");
+
+            board.undo_move(black_move);
+            board.undo_move(white_move);
+
+            assert_eq!(&board, fen);
+        }
+
         #[test]
         fn reset_castling_rights_when_moving_rooks() {
             let fen = "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let h1_move = Move::new(square!(H1), square!(G1), MoveFlag::None);
             let h8_move = Move::new(square!(H8), square!(G8), MoveFlag::None);
             let a1_move = Move::new(square!(A1), square!(B1), MoveFlag::None);
@@ -144,8 +158,7 @@ mod tests {
         #[test]
         fn reset_castling_rights_when_moving_king() {
             let fen = "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let white_move = Move::new(square!(E1), square!(D1), MoveFlag::None);
             let black_move = Move::new(square!(E8), square!(D8), MoveFlag::None);
 
@@ -161,8 +174,7 @@ mod tests {
         #[test]
         fn reset_castling_rights_after_rooks_captured() {
             let fen = "r3k2r/8/8/3BB3/3bb3/8/8/R3K2R w KQkq - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let h8_capture = Move::new(square!(E5), square!(H8), MoveFlag::None);
             let h1_capture = Move::new(square!(E4), square!(H1), MoveFlag::None);
             let a8_capture = Move::new(square!(D5), square!(A8), MoveFlag::None);
@@ -188,8 +200,7 @@ mod tests {
         #[test]
         fn queen() {
             let fen = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let r#move = Move::new(square!(A7), square!(A8), MoveFlag::PromoteQueen);
 
             board.make_move(r#move);
@@ -202,8 +213,7 @@ mod tests {
         #[test]
         fn rook() {
             let fen = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let r#move = Move::new(square!(A7), square!(A8), MoveFlag::PromoteRook);
 
             board.make_move(r#move);
@@ -216,8 +226,7 @@ mod tests {
         #[test]
         fn bishop() {
             let fen = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let r#move = Move::new(square!(A7), square!(A8), MoveFlag::PromoteBishop);
 
             board.make_move(r#move);
@@ -230,8 +239,7 @@ mod tests {
         #[test]
         fn knight() {
             let fen = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let r#move = Move::new(square!(A7), square!(A8), MoveFlag::PromoteKnight);
 
             board.make_move(r#move);
@@ -244,8 +252,7 @@ mod tests {
         #[test]
         fn queen_with_capture() {
             let fen = "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
-            let computed = Computed::new();
-            let mut board = Board::from_fen(fen, &computed);
+            let mut board = Board::from_fen(fen);
             let r#move = Move::new(square!(A7), square!(B8), MoveFlag::PromoteQueen);
 
             board.make_move(r#move);
@@ -255,4 +262,119 @@ mod tests {
             assert_eq!(&board, fen);
         }
     }
+
+    mod random {
+        use super::*;
+        use proptest::prelude::*;
+
+        const PIECES: [Piece; 10] = [
+            WHITE_QUEEN,
+            WHITE_ROOK,
+            WHITE_BISHOP,
+            WHITE_KNIGHT,
+            WHITE_PAWN,
+            BLACK_QUEEN,
+            BLACK_ROOK,
+            BLACK_BISHOP,
+            BLACK_KNIGHT,
+            BLACK_PAWN,
+        ];
+
+        fn assert_same(board: &Board, expected: &Board) -> Result<(), TestCaseError> {
+            prop_assert_eq!(board.turn, expected.turn);
+            prop_assert_eq!(board.squares, expected.squares);
+            prop_assert_eq!(board.pieces, expected.pieces);
+            prop_assert_eq!(board.colors, expected.colors);
+            prop_assert_eq!(&board.states, &expected.states);
+            Ok(())
+        }
+
+        // A board read back from its own FEN has a fresh history, everything else must match
+        fn assert_fen_round_trip(board: &Board) -> Result<(), TestCaseError> {
+            let fen = board.to_fen();
+            let parsed = Board::from_fen(&fen);
+
+            prop_assert_eq!(parsed.to_fen(), fen);
+            prop_assert_eq!(parsed.squares, board.squares);
+            prop_assert_eq!(parsed.get_checkers(), board.get_checkers());
+            prop_assert_eq!(parsed.get_pinned(), board.get_pinned());
+            prop_assert_eq!(parsed.get_key(), board.get_key());
+            Ok(())
+        }
+
+        // Every ply is made, undone and made again
+        fn play(mut board: Board, choices: Vec<usize>) -> Result<(), TestCaseError> {
+            playout(&mut board, &choices, |board, _, r#move| {
+                assert_fen_round_trip(board)?;
+
+                if let Some(r#move) = r#move {
+                    let before = board.clone();
+
+                    board.make_move(r#move);
+                    board.undo_move(r#move);
+                    assert_same(board, &before)?;
+                }
+
+                Ok(())
+            })
+        }
+
+        // Kings on two squares plus any other pieces, kept only when Board::validate() accepts them
+        fn random_board(
+            kings: (u8, u8),
+            pieces: Vec<(u8, Piece)>,
+            turn: PieceColor,
+        ) -> Option<Board> {
+            let mut board = Board::new();
+            board.states.push(BoardState::new());
+            board.set_square(kings.0, WHITE_KING);
+            board.set_square(kings.1, BLACK_KING);
+
+            for (square, piece) in pieces {
+                // Skipped rather than rejected, or most boards would fail validation on them
+                let back_rank = matches!(square >> 3, 0 | 7);
+
+                if board.squares[square as usize].is_none()
+                    && !(back_rank && piece.get_type() == PieceType::Pawn)
+                {
+                    board.set_square(square, piece);
+                }
+            }
+
+            // A king left in check is fine as long as it's that side's turn
+            [turn, turn.opposite()].into_iter().find_map(|turn| {
+                board.turn = turn;
+                Board::try_from_fen_strict(&board.to_fen()).ok()
+            })
+        }
+
+        fn kings() -> impl Strategy<Value = (u8, u8)> {
+            (0..64u8, 0..64u8).prop_filter("same square", |(white, black)| white != black)
+        }
+
+        fn pieces() -> impl Strategy<Value = Vec<(u8, Piece)>> {
+            prop::collection::vec((0..64u8, prop::sample::select(&PIECES[..])), 0..12)
+        }
+
+        proptest! {
+            #[test]
+            fn perft_positions(board in perft_board(), choices in choices(100)) {
+                play(board, choices)?;
+            }
+
+            #[test]
+            fn generated_positions(
+                kings in kings(),
+                pieces in pieces(),
+                white in any::<bool>(),
+                choices in choices(60),
+            ) {
+                let turn = if white { PieceColor::White } else { PieceColor::Black };
+                let board = random_board(kings, pieces, turn);
+
+                prop_assume!(board.is_some());
+                play(board.unwrap(), choices)?;
+            }
+        }
+    }
 }
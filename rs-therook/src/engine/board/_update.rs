@@ -1,83 +1,130 @@
 use super::*;
 
-impl Board<'_> {
-    pub fn update_attacks(&mut self, color: PieceColor) {
-        let mut attacks = Bitboard::new();
-        let mut check_state = CheckState::None;
-
+impl Board {
+    // Only the side to move can be in check or have pinned pieces that matter, so this runs once
+    // per make_move and the result lives in the state, where undo_move gets it back for free
+    pub(crate) fn update_checkers_and_pinned(&mut self) {
+        let color = self.turn;
         let enemy = color.opposite();
-        let enemy_king = self.pieces[enemy | PieceType::King];
-        let occupancy = self.colors[color] | self.colors[enemy];
-
-        for r#type in PieceType::ALL {
-            for square in self.pieces[color | r#type] {
-                let _attacks =
-                    self.computed
-                        .attacks
-                        .get(color, r#type, square, occupancy ^ enemy_king);
-
-                attacks |= _attacks;
-
-                if (_attacks & enemy_king).is_some() {
-                    check_state = match self.check_state[enemy] {
-                        CheckState::None => CheckState::Single(square),
-                        CheckState::Single(_) => CheckState::Double,
-                        CheckState::Double => unreachable!(),
-                    }
-                }
-            }
-        }
 
-        self.check_state[enemy] = check_state;
+        // Nothing can be checked or pinned without a king, which Board::validate() reports
+        let (checkers, pinned) = match u8::try_from(self.pieces[color | PieceType::King]) {
+            Ok(king_square) => {
+                let occupancy = self.colors[color] | self.colors[enemy];
 
-        if attacks == self.attacks[color] {
-            log::warn!("Board::update_attacks() called but attacks didn't change");
-        } else {
-            self.attacks[color] = attacks;
-        }
+                (
+                    self.get_attackers(king_square, occupancy) & self.colors[enemy],
+                    self.calculate_pinned(color, king_square),
+                )
+            }
+            Err(_) => (Bitboard::new(), Bitboard::new()),
+        };
+
+        let state = self.states.last_mut().unwrap();
+        state.checkers = checkers;
+        state.pinned = pinned;
     }
 
+    // Pieces that are the only thing standing between their king and an enemy slider
     // https://www.chessprogramming.org/Checks_and_Pinned_Pieces_%28Bitboards%29#Absolute_Pins
-    pub fn update_pin_lines(&mut self, color: PieceColor) {
-        let mut pin_lines = vec![];
+    fn calculate_pinned(&self, color: PieceColor, king_square: u8) -> Bitboard {
+        let mut pinned = Bitboard::new();
 
         let enemy = color.opposite();
-        let king_square = u8::try_from(self.pieces[color | PieceType::King]).unwrap();
-
         let friendlies = self.colors[color];
         let enemies = self.colors[enemy];
         let occupancy = friendlies | enemies;
 
-        let mut pinners = self
-            .computed
-            .xray_orthogonal_attacks(occupancy, friendlies, king_square);
-        pinners &= self.pieces[enemy | PieceType::Queen] | self.pieces[enemy | PieceType::Rook];
+        let mut orthogonal = COMPUTED.xray_orthogonal_attacks(occupancy, friendlies, king_square);
+        orthogonal &= self.pieces[enemy | PieceType::Queen] | self.pieces[enemy | PieceType::Rook];
 
-        self.clear_pinners(&mut pin_lines, &mut pinners, king_square);
+        let mut diagonal = COMPUTED.xray_diagonal_attacks(occupancy, friendlies, king_square);
+        diagonal &= self.pieces[enemy | PieceType::Queen] | self.pieces[enemy | PieceType::Bishop];
 
-        let mut pinners = self
-            .computed
-            .xray_diagonal_attacks(occupancy, friendlies, king_square);
-        pinners &= self.pieces[enemy | PieceType::Queen] | self.pieces[enemy | PieceType::Bishop];
+        // Each pinner x-rays through exactly one friendly piece on its way to the king
+        for pinner in orthogonal | diagonal {
+            pinned |= COMPUTED.betweens.get(pinner, king_square) & friendlies;
+        }
 
-        self.clear_pinners(&mut pin_lines, &mut pinners, king_square);
+        pinned
+    }
 
-        if pin_lines == self.pin_lines[color] {
-            log::warn!("Board::update_pin_lines() called but pin lines didn't change");
-        } else {
-            self.pin_lines[color] = pin_lines;
-        }
+    // Pieces of either color attacking a square, found by looking outwards from it
+    // https://www.chessprogramming.org/Square_Attacked_By#AnyAttackBySide
+    pub fn get_attackers(&self, square: u8, occupancy: Bitboard) -> Bitboard {
+        let attacks = &COMPUTED.attacks;
+        let pieces = &self.pieces;
+
+        let knights = pieces[WHITE_KNIGHT] | pieces[BLACK_KNIGHT];
+        let kings = pieces[WHITE_KING] | pieces[BLACK_KING];
+        let queens = pieces[WHITE_QUEEN] | pieces[BLACK_QUEEN];
+        let rooks = pieces[WHITE_ROOK] | pieces[BLACK_ROOK] | queens;
+        let bishops = pieces[WHITE_BISHOP] | pieces[BLACK_BISHOP] | queens;
+
+        // Pawns attack backwards from the square the way the other color's pawns would
+        let white = PieceColor::White;
+        let black = PieceColor::Black;
+        (attacks.get(white, PieceType::Pawn, square, occupancy) & pieces[BLACK_PAWN])
+            | (attacks.get(black, PieceType::Pawn, square, occupancy) & pieces[WHITE_PAWN])
+            | (attacks.get(white, PieceType::Knight, square, occupancy) & knights)
+            | (attacks.get(white, PieceType::King, square, occupancy) & kings)
+            | (attacks.get(white, PieceType::Rook, square, occupancy) & rooks)
+            | (attacks.get(white, PieceType::Bishop, square, occupancy) & bishops)
     }
 
-    fn clear_pinners(
-        &mut self,
-        pin_lines: &mut Vec<Bitboard>,
-        pinners: &mut Bitboard,
-        king_square: u8,
-    ) {
-        for pinner in pinners {
-            pin_lines
-                .push(self.computed.betweens.get(pinner, king_square) | Bitboard::from(pinner));
+    // Whether a color attacks a square, stopping at the first piece type that does. Pawns attack
+    // backwards from the square the way the other color's pawns would
+    pub fn is_attacked(&self, square: u8, occupancy: Bitboard, color: PieceColor) -> bool {
+        let pieces = &self.pieces;
+        let queens = pieces[color | PieceType::Queen];
+
+        let attacked_by = |attack_color: PieceColor, r#type: PieceType, attackers: Bitboard| {
+            (COMPUTED
+                .attacks
+                .get(attack_color, r#type, square, occupancy)
+                & attackers)
+                .is_some()
+        };
+
+        attacked_by(
+            color.opposite(),
+            PieceType::Pawn,
+            pieces[color | PieceType::Pawn],
+        ) || attacked_by(color, PieceType::Knight, pieces[color | PieceType::Knight])
+            || attacked_by(color, PieceType::King, pieces[color | PieceType::King])
+            || attacked_by(
+                color,
+                PieceType::Bishop,
+                pieces[color | PieceType::Bishop] | queens,
+            )
+            || attacked_by(
+                color,
+                PieceType::Rook,
+                pieces[color | PieceType::Rook] | queens,
+            )
+    }
+
+    pub fn get_checkers(&self) -> Bitboard {
+        self.get_state().checkers
+    }
+
+    pub fn get_pinned(&self) -> Bitboard {
+        self.get_state().pinned
+    }
+
+    pub fn get_check_state(&self) -> CheckState {
+        let mut checkers = self.get_checkers();
+
+        match (checkers.next(), checkers.next()) {
+            (None, _) => CheckState::None,
+            (Some(checker), None) => CheckState::Single(checker),
+            // More than two checkers only happen after making a pseudo-legal move that leaves the
+            // king in check, which is then taken back
+            (Some(_), Some(_)) => CheckState::Double,
         }
     }
+
+    pub fn is_in_check(&self) -> bool {
+        self.get_checkers().is_some()
+    }
 }
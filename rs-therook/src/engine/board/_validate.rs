@@ -0,0 +1,231 @@
+use super::*;
+
+impl Board {
+    // Positions that parse fine but can never be reached from the initial position.
+    // Move generation assumes none of these hold, so check before searching untrusted input
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        let state = self.get_state();
+
+        for color in PieceColor::ALL {
+            match self.pieces[color | PieceType::King].count() {
+                0 => violations.push(Violation::MissingKing(color)),
+                1 => {}
+                _ => violations.push(Violation::TooManyKings(color)),
+            }
+
+            if self.colors[color].count() > 16 {
+                violations.push(Violation::TooManyPieces(color));
+            }
+
+            if self.pieces[color | PieceType::Pawn].count() > 8 {
+                violations.push(Violation::TooManyPawns(color));
+            }
+        }
+
+        let pawns = self.pieces[WHITE_PAWN] | self.pieces[BLACK_PAWN];
+        for square in pawns & (RANK_1 | RANK_8) {
+            violations.push(Violation::PawnOnBackRank(square));
+        }
+
+        // The side that just moved cannot have left its own king in check
+        let occupancy = self.colors[PieceColor::White] | self.colors[PieceColor::Black];
+        let king = self.pieces[self.turn.opposite() | PieceType::King];
+        let is_attacked = |square: u8| self.is_attacked(square, occupancy, self.turn);
+        if king.into_iter().any(is_attacked) {
+            violations.push(Violation::OpponentInCheck);
+        }
+
+        // The rook has to be on its side of the king on the back rank, and outside of Chess960 both
+        // pieces have to be on the squares they start on
+        for castle in [WHITE_KING, WHITE_QUEEN, BLACK_KING, BLACK_QUEEN] {
+            let Some(rook) = self.get_castling_rook(castle) else {
+                continue;
+            };
+
+            let color = castle.get_color();
+            let is_valid = u8::try_from(self.pieces[color | PieceType::King]).is_ok_and(|king| {
+                king >> 3 == color.get_home_row()
+                    && (rook > king) == (castle.get_type() == PieceType::King)
+                    && (self.chess960 || (king & 7 == 4 && matches!(rook & 7, 0 | 7)))
+            });
+
+            if !is_valid || self.squares[rook as usize] != Some(color | PieceType::Rook) {
+                violations.push(Violation::InvalidCastlingRights(castle));
+            }
+        }
+
+        // The enemy pawn must have just double pushed through the en passant square
+        if let Ok(square) = u8::try_from(state.enpassant) {
+            let enemy = self.turn.opposite();
+            let rank = match self.turn {
+                PieceColor::White => 5,
+                PieceColor::Black => 2,
+            };
+
+            // Only squares on the right rank have both neighbouring squares on the board
+            let is_valid = square >> 3 == rank && {
+                let (pushed, start) = match self.turn {
+                    PieceColor::White => (square - 8, square + 8),
+                    PieceColor::Black => (square + 8, square - 8),
+                };
+
+                self.squares[square as usize].is_none()
+                    && self.squares[start as usize].is_none()
+                    && self.squares[pushed as usize] == Some(enemy | PieceType::Pawn)
+            };
+
+            if !is_valid {
+                violations.push(Violation::InvalidEnPassant(square));
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(fen: &str) -> Vec<Violation> {
+        Board::from_fen(fen).validate()
+    }
+
+    #[test]
+    fn legal() {
+        assert_eq!(
+            validate("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+            vec![]
+        );
+        assert_eq!(
+            validate("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3"),
+            vec![]
+        );
+        assert_eq!(
+            validate("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn kings() {
+        assert_eq!(
+            validate("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+            vec![Violation::MissingKing(PieceColor::Black)]
+        );
+        assert_eq!(
+            validate("8/8/8/8/8/8/8/8 w - - 0 1"),
+            vec![
+                Violation::MissingKing(PieceColor::White),
+                Violation::MissingKing(PieceColor::Black)
+            ]
+        );
+    }
+
+    #[test]
+    fn piece_counts() {
+        assert_eq!(
+            validate("4k3/8/8/8/8/P7/PPPPPPPP/4K3 w - - 0 1"),
+            vec![Violation::TooManyPawns(PieceColor::White)]
+        );
+        assert_eq!(
+            validate("4k3/8/8/8/8/NN6/NNNNNNNN/NNNNKNNN w - - 0 1"),
+            vec![Violation::TooManyPieces(PieceColor::White)]
+        );
+    }
+
+    #[test]
+    fn pawns_on_back_rank() {
+        assert_eq!(
+            validate("P3k3/8/8/8/8/8/8/4K2p w - - 0 1"),
+            vec![
+                Violation::PawnOnBackRank(square!(H1)),
+                Violation::PawnOnBackRank(square!(A8))
+            ]
+        );
+    }
+
+    #[test]
+    fn opponent_in_check() {
+        assert_eq!(validate("4k3/8/8/8/8/8/8/4K2R w - - 0 1"), vec![]);
+        assert_eq!(
+            validate("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1"),
+            vec![Violation::OpponentInCheck]
+        );
+    }
+
+    #[test]
+    fn castling_rights() {
+        assert_eq!(
+            validate("r3k3/8/8/8/8/8/8/4K2R w KQkq - 0 1"),
+            vec![
+                Violation::InvalidCastlingRights(WHITE_QUEEN),
+                Violation::InvalidCastlingRights(BLACK_KING)
+            ]
+        );
+        assert_eq!(
+            validate("r2k4/8/8/8/8/8/8/R3K2R w KQkq - 0 1"),
+            vec![Violation::InvalidCastlingRights(BLACK_KING)]
+        );
+        assert_eq!(
+            validate("4k3/8/8/8/8/8/8/1K5R w KQ - 0 1"),
+            vec![Violation::InvalidCastlingRights(WHITE_QUEEN)]
+        );
+
+        // The king may start on any file between its rooks in Chess960
+        assert_eq!(validate("r2k3r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"), vec![]);
+        assert_eq!(validate("1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1"), vec![]);
+
+        let mut board = Board::from_fen("rk5r/8/8/8/8/8/8/RK5R w KQkq - 0 1");
+        assert_eq!(board.validate(), vec![]);
+
+        board.chess960 = false;
+        assert_eq!(
+            board.validate(),
+            vec![
+                Violation::InvalidCastlingRights(WHITE_KING),
+                Violation::InvalidCastlingRights(WHITE_QUEEN),
+                Violation::InvalidCastlingRights(BLACK_KING),
+                Violation::InvalidCastlingRights(BLACK_QUEEN)
+            ]
+        );
+    }
+
+    #[test]
+    fn enpassant() {
+        // No pawn that could have double pushed
+        assert_eq!(
+            validate("4k3/8/8/8/8/8/8/4K3 b - e3 0 1"),
+            vec![Violation::InvalidEnPassant(square!(E3))]
+        );
+
+        // Wrong side to move for the square
+        assert_eq!(
+            validate("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"),
+            vec![Violation::InvalidEnPassant(square!(E3))]
+        );
+
+        // Square the pawn came from is occupied
+        assert_eq!(
+            validate("4k3/8/8/8/4P3/8/4N3/4K3 b - e3 0 1"),
+            vec![Violation::InvalidEnPassant(square!(E3))]
+        );
+
+        // A hand-built state can put the square on a back rank, which no FEN allows
+        for (turn, square) in [
+            (PieceColor::White, square!(E8)),
+            (PieceColor::Black, square!(E1)),
+        ] {
+            let mut board = Board::from_fen("3k4/8/8/8/8/8/8/3K4 w - - 0 1");
+            board.turn = turn;
+            board.states.last_mut().unwrap().enpassant = Bitboard::from(square);
+
+            assert!(
+                board
+                    .validate()
+                    .contains(&Violation::InvalidEnPassant(square))
+            );
+        }
+    }
+}
@@ -0,0 +1,15 @@
+// Which of the legal moves to generate. Captures and Quiets split every position's moves between
+// them, with queen promotions counted as captures since quiescence search can't skip them
+// https://www.chessprogramming.org/Move_Generation#Staged_Move_Generation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenType {
+    All,
+    // Captures, including en passant, and promotions to a queen
+    Captures,
+    // Everything else, including castling and underpromotions that don't capture
+    Quiets,
+    // Every legal move while in check, and none otherwise
+    Evasions,
+    // The quiet moves that give check
+    QuietChecks,
+}
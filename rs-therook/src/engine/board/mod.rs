@@ -1,59 +1,59 @@
 mod _calculate_moves;
+mod _castling;
 mod _debug;
+mod _gives_check;
 mod _index;
+mod _key;
 mod _make_move;
+mod _outcome;
 mod _undo_move;
 mod _update;
+mod _validate;
 mod check_state;
+mod gen_type;
+mod outcome;
 mod state;
+mod violation;
 
 use super::*;
 pub use check_state::*;
+pub use gen_type::*;
+pub use outcome::*;
 pub use state::*;
+pub use violation::*;
 
 #[derive(Clone)]
-pub struct Board<'a> {
-    // Pre-computed data
-    pub computed: &'a Computed,
-
+pub struct Board {
     // Core information
     pub turn: PieceColor,
     pub squares: [Option<Piece>; 64],
+    // Castling moves are written as the king capturing its own rook in UCI
+    pub chess960: bool,
 
     // Calculated on the fly
     pub pieces: [Bitboard; 12],
     pub colors: [Bitboard; 2],
-    pub attacks: [Bitboard; 2],
-    pub pin_lines: [Vec<Bitboard>; 2],
-    pub check_state: [CheckState; 2],
 
     // For undoing and restoration of state
     pub states: Vec<BoardState>,
 }
 
-impl<'a> Board<'a> {
-    pub fn new(computed: &'a Computed) -> Self {
+impl Board {
+    pub fn new() -> Self {
         Board {
-            computed,
-
             turn: PieceColor::White,
             squares: [None; 64],
+            chess960: false,
 
             pieces: [Bitboard::new(); 12],
             colors: [Bitboard::new(); 2],
-            attacks: [Bitboard::new(); 2],
-            pin_lines: [vec![], vec![]],
-            check_state: [CheckState::None; 2],
 
             states: vec![],
         }
     }
 
-    pub fn initial(computed: &'a Computed) -> Self {
-        Board::from_fen(
-            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
-            computed,
-        )
+    pub fn initial() -> Self {
+        Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
     }
 
     pub fn get_state(&self) -> &BoardState {
@@ -62,6 +62,10 @@ impl<'a> Board<'a> {
             .unwrap_or_else(|| panic!("No board state..."))
     }
 
+    pub fn get_key(&self) -> u64 {
+        self.get_state().key
+    }
+
     pub fn set_square(&mut self, square: u8, piece: Piece) {
         let bitboard = Bitboard::from(square);
         let color = piece.get_color();
@@ -69,6 +73,10 @@ impl<'a> Board<'a> {
         self.squares[square as usize] = Some(piece);
         self.pieces[piece] |= bitboard;
         self.colors[color] |= bitboard;
+
+        if let Some(state) = self.states.last_mut() {
+            state.key ^= Zobrist::piece(piece, square);
+        }
     }
 
     pub fn clear_square(&mut self, square: u8, piece: Piece) {
@@ -78,5 +86,17 @@ impl<'a> Board<'a> {
         self.squares[square as usize] = None;
         self.pieces[piece] ^= bitboard;
         self.colors[color] ^= bitboard;
+
+        if let Some(state) = self.states.last_mut() {
+            state.key ^= Zobrist::piece(piece, square);
+        }
+    }
+}
+
+// Board::new() has no state yet and is only a starting point for the FEN parser, so the default is
+// the position games start from instead
+impl Default for Board {
+    fn default() -> Self {
+        Self::initial()
     }
 }
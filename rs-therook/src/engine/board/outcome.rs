@@ -0,0 +1,32 @@
+use super::*;
+
+// https://www.chessprogramming.org/Draw
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Checkmate(PieceColor),
+    Stalemate,
+    InsufficientMaterial,
+    FivefoldRepetition,
+    SeventyFiveMoveRule,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+}
+
+impl Outcome {
+    pub fn get_winner(&self) -> Option<PieceColor> {
+        match self {
+            Outcome::Checkmate(winner) => Some(*winner),
+            _ => None,
+        }
+    }
+
+    pub fn is_draw(&self) -> bool {
+        self.get_winner().is_none()
+    }
+
+    // Threefold repetition and the fifty-move rule only end the game once a player claims them,
+    // everything else ends the game immediately
+    pub fn is_claimable(&self) -> bool {
+        matches!(self, Outcome::ThreefoldRepetition | Outcome::FiftyMoveRule)
+    }
+}
@@ -1,24 +1,40 @@
 use super::*;
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BoardState {
-    pub castling: [bool; 4],
+    // File of the rook each right castles with, which is only fixed in standard chess
+    pub castling: [Option<u8>; 4],
     pub enpassant: Bitboard,
-    pub halfmove: u8,
-    pub fullmove: u8,
+    pub halfmove: u16,
+    pub fullmove: u16,
+    pub key: u64,
 
     pub captured: Option<Piece>,
+
+    // Only for the side to move, so undoing a move gets them back with the rest of the state
+    pub checkers: Bitboard,
+    pub pinned: Bitboard,
 }
 
 impl BoardState {
     pub fn new() -> Self {
         BoardState {
-            castling: [false; 4],
+            castling: [None; 4],
             enpassant: Bitboard::new(),
             halfmove: 0,
             fullmove: 1,
+            key: 0,
 
             captured: None,
+
+            checkers: Bitboard::new(),
+            pinned: Bitboard::new(),
         }
     }
 }
+
+impl Default for BoardState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    MissingKing(PieceColor),
+    TooManyKings(PieceColor),
+    TooManyPieces(PieceColor),
+    TooManyPawns(PieceColor),
+    PawnOnBackRank(u8),
+    OpponentInCheck,
+    // Castling right of the king or queen side, as indexed in BoardState::castling
+    InvalidCastlingRights(Piece),
+    InvalidEnPassant(u8),
+}
@@ -1,36 +1,25 @@
 use super::*;
-use std::num::Wrapping;
 
 pub struct Attacks {
-    rays: Rays,
-
     kings: [Bitboard; 64],
     knights: [Bitboard; 64],
     white_pawns: [Bitboard; 64],
     black_pawns: [Bitboard; 64],
 
-    ranks: [[Bitboard; 64]; 64],
-    files: [[Bitboard; 64]; 64],
-    diagonals: [[Bitboard; 64]; 64],
-    antidiags: [[Bitboard; 64]; 64],
+    rooks: Magics,
+    bishops: Magics,
 }
 
 impl Attacks {
-    const FILE_B: u64 = 0x0202020202020202;
-
     pub fn new() -> Self {
         let mut masks = Attacks {
-            rays: Rays::new(),
-
             kings: [Bitboard::new(); 64],
             knights: [Bitboard::new(); 64],
             white_pawns: [Bitboard::new(); 64],
             black_pawns: [Bitboard::new(); 64],
 
-            ranks: [[Bitboard::new(); 64]; 64],
-            files: [[Bitboard::new(); 64]; 64],
-            diagonals: [[Bitboard::new(); 64]; 64],
-            antidiags: [[Bitboard::new(); 64]; 64],
+            rooks: Magics::rooks(),
+            bishops: Magics::bishops(),
         };
 
         for square in 0..64usize {
@@ -55,46 +44,6 @@ impl Attacks {
             // https://www.chessprogramming.org/Pawn_Attacks_(Bitboards)#Attacks_2
             masks.white_pawns[square] = bitboard.north_east() | bitboard.north_west();
             masks.black_pawns[square] = bitboard.south_east() | bitboard.south_west();
-
-            let rank = square >> 3;
-            let file = square & 7;
-            let slider = 1 << file;
-            for occupancy in 0..64 {
-                // https://www.chessprogramming.org/Efficient_Generation_of_Sliding_Piece_Attacks#Lookup_Techniques
-                let o = Wrapping((occupancy as u8) << 1);
-                let s = Wrapping(slider as u8);
-                let _2 = Wrapping(2u8);
-
-                // https://www.chessprogramming.org/Efficient_Generation_of_Sliding_Piece_Attacks#Sliding_Attacks_by_Calculation
-                let single_rank = ((o - _2 * s)
-                    ^ (o.reverse_bits() - _2 * s.reverse_bits()).reverse_bits())
-                .0 as u64;
-                let single_rank_files = single_rank.wrapping_mul(FILE_A.into());
-                let single_file_ranks = u64::from(Bitboard::from(single_rank_files).clockwise());
-
-                masks.ranks[square][occupancy] = masks.rays.ranks[square] & single_rank_files;
-
-                // Generated through 2 days of blood sweat and tears worth of testing
-                let file_square = rank + ((7 - file) * 8);
-
-                masks.files[file_square][occupancy] =
-                    (FILE_A << (square as u8 >> 3)) & single_file_ranks;
-
-                // https://www.chessprogramming.org/On_an_empty_Board#By_Calculation_3
-                masks.diagonals[square][occupancy] = {
-                    let diagonal = 8 * (square & 7) as i32 - (square & 56) as i32;
-                    let north = (-diagonal & (diagonal >> 31)) as u8;
-                    let south = (diagonal & (-diagonal >> 31)) as u8;
-                    ((DIAGONAL_MAIN >> south) << north) & single_rank_files
-                };
-
-                masks.antidiags[square][occupancy] = {
-                    let diagonal = 56 - 8 * (square & 7) as i32 - (square & 56) as i32;
-                    let north = (-diagonal & (diagonal >> 31)) as u8;
-                    let south = (diagonal & (-diagonal >> 31)) as u8;
-                    ((ANTIDIAG_MAIN >> south) << north) & single_rank_files
-                };
-            }
         }
 
         masks
@@ -107,48 +56,25 @@ impl Attacks {
         square: u8,
         occupancy: Bitboard,
     ) -> Bitboard {
-        let square = square as usize;
-
         match r#type {
-            PieceType::King => self.kings[square],
+            PieceType::King => self.kings[square as usize],
             PieceType::Queen => {
-                let rank = occupancy & self.rays.ranks[square];
-                let file = occupancy & self.rays.files[square];
-                let file = file.anticlockwise();
-                let diagonal = occupancy & self.rays.diagonals[square];
-                let antidiag = occupancy & self.rays.antidiags[square];
-
-                self.ranks[square][Self::kindergarten(rank)]
-                    | self.files[square][Self::kindergarten(file)]
-                    | self.diagonals[square][Self::kindergarten(diagonal)]
-                    | self.antidiags[square][Self::kindergarten(antidiag)]
+                self.rooks.get(square, occupancy) | self.bishops.get(square, occupancy)
             }
-            PieceType::Rook => {
-                let rank = occupancy & self.rays.ranks[square];
-                let file = occupancy & self.rays.files[square];
-                let file = file.anticlockwise();
-
-                self.ranks[square][Self::kindergarten(rank)]
-                    | self.files[square][Self::kindergarten(file)]
-            }
-            PieceType::Bishop => {
-                let diagonal = occupancy & self.rays.diagonals[square];
-                let antidiag = occupancy & self.rays.antidiags[square];
-
-                self.diagonals[square][Self::kindergarten(diagonal)]
-                    | self.antidiags[square][Self::kindergarten(antidiag)]
-            }
-            PieceType::Knight => self.knights[square],
+            PieceType::Rook => self.rooks.get(square, occupancy),
+            PieceType::Bishop => self.bishops.get(square, occupancy),
+            PieceType::Knight => self.knights[square as usize],
             PieceType::Pawn => match color {
-                PieceColor::White => self.white_pawns[square],
-                PieceColor::Black => self.black_pawns[square],
+                PieceColor::White => self.white_pawns[square as usize],
+                PieceColor::Black => self.black_pawns[square as usize],
             },
         }
     }
+}
 
-    // https://www.chessprogramming.org/Efficient_Generation_of_Sliding_Piece_Attacks#Kindergarten_Bitboards
-    fn kindergarten(bitboard: Bitboard) -> usize {
-        (u64::from(bitboard).wrapping_mul(Self::FILE_B) >> 58) as usize
+impl Default for Attacks {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -156,6 +82,7 @@ impl Attacks {
 mod tests {
     use super::*;
     use itertools::Itertools;
+    use proptest::prelude::*;
 
     #[test]
     fn kings() {
@@ -307,11 +234,12 @@ mod tests {
     #[test]
     fn ranks_files_alone() {
         let masks = Attacks::new();
+        let rays = Rays::new();
 
         for square in 0..64u8 {
             let bitboard = Bitboard::from(square);
             assert_eq!(
-                (masks.rays.ranks[square as usize] | masks.rays.files[square as usize]) ^ bitboard,
+                (rays.ranks[square as usize] | rays.files[square as usize]) ^ bitboard,
                 masks.get(PieceColor::White, PieceType::Rook, square, bitboard)
             );
         }
@@ -347,12 +275,12 @@ mod tests {
     #[test]
     fn diagonals_antidiags_alone() {
         let masks = Attacks::new();
+        let rays = Rays::new();
 
         for square in 0..64u8 {
             let bitboard = Bitboard::from(square);
             assert_eq!(
-                (masks.rays.diagonals[square as usize] | masks.rays.antidiags[square as usize])
-                    ^ bitboard,
+                (rays.diagonals[square as usize] | rays.antidiags[square as usize]) ^ bitboard,
                 masks.get(PieceColor::White, PieceType::Bishop, square, bitboard)
             );
         }
@@ -361,11 +289,12 @@ mod tests {
     #[test]
     fn diagonals_antidiags_with_pieces() {
         let masks = Attacks::new();
+        let rays = Rays::new();
 
         for square in 0..64u8 {
             let bitboard = Bitboard::from(square);
 
-            let diagonal = masks.rays.diagonals[square as usize];
+            let diagonal = rays.diagonals[square as usize];
             let diagonal_occupancies = (1..=diagonal.count())
                 .flat_map(|l| diagonal.into_iter().combinations(l))
                 .map(|ts| {
@@ -375,7 +304,7 @@ mod tests {
                 .filter(|b| (*b & bitboard).is_some())
                 .collect::<Vec<_>>();
 
-            let antidiag = masks.rays.antidiags[square as usize];
+            let antidiag = rays.antidiags[square as usize];
             let antidiag_occupancies = (1..=antidiag.count())
                 .flat_map(|l| antidiag.into_iter().combinations(l))
                 .map(|ts| {
@@ -398,6 +327,26 @@ mod tests {
         }
     }
 
+    proptest! {
+        // The tests above only fill the lines through the square, these also scatter pieces over
+        // the rest of the board, which the magic index has to ignore
+        #[test]
+        fn sliders_with_random_occupancy(square in 0..64u8, occupancy in any::<u64>()) {
+            let masks = Attacks::new();
+            let occupancy = Bitboard::from(occupancy);
+            let orthogonals = walk_directions(vec![8, 1, -8, -1], square, occupancy);
+            let diagonals = walk_directions(vec![7, 9, -7, -9], square, occupancy);
+
+            for (r#type, expected) in [
+                (PieceType::Rook, orthogonals),
+                (PieceType::Bishop, diagonals),
+                (PieceType::Queen, orthogonals | diagonals),
+            ] {
+                prop_assert_eq!(masks.get(PieceColor::White, r#type, square, occupancy), expected);
+            }
+        }
+    }
+
     fn walk_directions(directions: Vec<i8>, square: u8, occupancy: Bitboard) -> Bitboard {
         let mut expected = Bitboard::new();
 
@@ -39,6 +39,12 @@ impl Betweens {
     }
 }
 
+impl Default for Betweens {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,75 @@
+use super::*;
+
+// Whole rank, file or diagonal through two squares from edge to edge, empty when they don't share
+// one. A pinned piece has to stay on the line through its king
+// https://www.chessprogramming.org/Square_Attacked_By#LineBetween
+pub struct Lines {
+    data: [[Bitboard; 64]; 64],
+}
+
+impl Lines {
+    pub fn new() -> Self {
+        let rays = Rays::new();
+        let mut masks = Lines {
+            data: [[Bitboard::new(); 64]; 64],
+        };
+
+        for start in 0..64u8 {
+            let square = start as usize;
+
+            for line in [
+                rays.ranks[square],
+                rays.files[square],
+                rays.diagonals[square],
+                rays.antidiags[square],
+            ] {
+                for end in line ^ start {
+                    masks.data[square][end as usize] = line;
+                }
+            }
+        }
+
+        masks
+    }
+
+    pub fn get(&self, start: u8, end: u8) -> Bitboard {
+        self.data[start as usize][end as usize]
+    }
+}
+
+impl Default for Lines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn through_betweens() {
+        let attacks = Attacks::new();
+        let betweens = Betweens::new();
+        let lines = Lines::new();
+
+        for square in 0..64u8 {
+            let bitboard = Bitboard::from(square);
+            let reachable = attacks.get(PieceColor::White, PieceType::Queen, square, bitboard);
+
+            for _square in 0..64u8 {
+                let line = lines.get(square, _square);
+
+                if (reachable & _square).is_some() {
+                    let both = bitboard | Bitboard::from(_square);
+                    let between = betweens.get(square, _square);
+
+                    assert_eq!(line & (both | between), both | between);
+                    assert_eq!(line, lines.get(_square, square));
+                } else {
+                    assert_eq!(line, Bitboard::new());
+                }
+            }
+        }
+    }
+}
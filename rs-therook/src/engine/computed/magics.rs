@@ -0,0 +1,302 @@
+use super::*;
+
+// https://www.chessprogramming.org/Magic_Bitboards#Fancy
+// Found by trying sparse random numbers until no two occupancies with different attacks landed on
+// the same index, with the shift fixed to the number of relevant occupancy bits so the same table
+// layout also works for PEXT
+const ROOK_MAGICS: [u64; 64] = [
+    0x0080068051E04000,
+    0x0040001000402000,
+    0x0080100020008008,
+    0x4E000A0010208440,
+    0x4200040802002010,
+    0x0100010008020400,
+    0x9080608019000600,
+    0x8100020080204100,
+    0x4103800480400020,
+    0x8015004004802100,
+    0x000200108A002040,
+    0x0801000821001000,
+    0x0015000500080070,
+    0x0120800400800200,
+    0x0109000432001100,
+    0x020080055B000080,
+    0x0080004000402002,
+    0x5260848020004008,
+    0x2402020014402080,
+    0x3000808010000802,
+    0x0304018004810800,
+    0x0000808004000200,
+    0x0002040001500248,
+    0x0012020000408401,
+    0x8440008080004020,
+    0x0804200840100040,
+    0x0820008080201000,
+    0x2080100100082100,
+    0x0001000500100800,
+    0x00A1000900028400,
+    0x0100100400C80102,
+    0x000001120000A044,
+    0x800080C004800620,
+    0x4040081000202000,
+    0x0D08802008801000,
+    0x1000800800801004,
+    0x1004000801010010,
+    0x0402800400800200,
+    0x0004080204008110,
+    0x0000404082000401,
+    0x00C0118861408000,
+    0x1100220081020048,
+    0x09A0430420050010,
+    0x0000082200420010,
+    0x2110080004008080,
+    0x2004201040680104,
+    0x1106001451820008,
+    0x0002224104820014,
+    0x00800C8044210500,
+    0x02A0200040100040,
+    0x040100A0001E4100,
+    0x00204023108A0200,
+    0x2400080080040080,
+    0x1289008400020900,
+    0x0002088250010400,
+    0x0001006084010200,
+    0x0001023480002141,
+    0x0006400021810015,
+    0x8400100840200101,
+    0x40003000A1000825,
+    0x1002011008200402,
+    0x100D000400080201,
+    0x0020048806102904,
+    0x8401000020804201,
+];
+
+const BISHOP_MAGICS: [u64; 64] = [
+    0x4C40240122060016,
+    0x8048110404004A80,
+    0x8004440410414020,
+    0x021C410060405000,
+    0x80CD1040D0480812,
+    0x0002021104000082,
+    0x08440082A8200001,
+    0x00202A0800841002,
+    0x0200C40810842088,
+    0x60C0081000C08901,
+    0x00A3D0040042510C,
+    0x1C00110400808541,
+    0x0400820211084005,
+    0x0000008860080800,
+    0x002002020202C000,
+    0x0400344E08040A81,
+    0x812800102098A080,
+    0x00202010823A2040,
+    0x4086400800830201,
+    0x5008012A22004000,
+    0x0004801C00A00000,
+    0x0000400200505400,
+    0x0480408401080820,
+    0x8000400029082824,
+    0x0008880804501000,
+    0x0001600048084100,
+    0x0108220624040400,
+    0x0008080000820002,
+    0xC804040010410041,
+    0x01080A0040208400,
+    0x2018030480A88800,
+    0x4040410020410810,
+    0x1108044010100210,
+    0x084A100400029800,
+    0x0801080100820C00,
+    0x8010400808108200,
+    0x0084008400020500,
+    0x0002004200290481,
+    0x0010150200032090,
+    0x8404042220404102,
+    0x0302080308004008,
+    0x1200420820000408,
+    0x0802002024200800,
+    0x4020824208000084,
+    0x000002020C008200,
+    0x2C40208081000882,
+    0x2082223441000401,
+    0x8804080081101020,
+    0x4401011002220808,
+    0x81020C4202100000,
+    0x4005004404040308,
+    0x0820400C42020001,
+    0x0020206421820010,
+    0x0150401001424008,
+    0x02A20242020C0608,
+    0x5020110109011200,
+    0x2050840108410401,
+    0x0100090880842108,
+    0x220008960142187A,
+    0x1111028880208820,
+    0x4400200042028200,
+    0x4400010802084206,
+    0x0000400242040100,
+    0x0002201104010944,
+];
+
+const ORTHOGONALS: [(i8, i8); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const DIAGONALS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+// https://www.chessprogramming.org/BMI2#PEXTBitboards
+#[cfg(all(
+    feature = "pext",
+    not(all(target_arch = "x86_64", target_feature = "bmi2"))
+))]
+compile_error!("The pext feature needs BMI2, build with RUSTFLAGS=\"-C target-feature=+bmi2\"");
+
+#[derive(Clone, Copy)]
+struct Magic {
+    mask: Bitboard,
+    #[cfg(not(feature = "pext"))]
+    magic: u64,
+    #[cfg(not(feature = "pext"))]
+    shift: u8,
+    offset: usize,
+}
+
+impl Magic {
+    #[cfg(not(feature = "pext"))]
+    fn index(&self, occupancy: Bitboard) -> usize {
+        let relevant = u64::from(occupancy & self.mask);
+        self.offset + (relevant.wrapping_mul(self.magic) >> self.shift) as usize
+    }
+
+    #[cfg(feature = "pext")]
+    fn index(&self, occupancy: Bitboard) -> usize {
+        use std::arch::x86_64::_pext_u64;
+
+        // Safe because the build fails above unless BMI2 is enabled for the target
+        let index = unsafe { _pext_u64(u64::from(occupancy), u64::from(self.mask)) };
+
+        self.offset + index as usize
+    }
+}
+
+// Slider attacks for every square and every occupancy that matters, all squares sharing one table
+pub struct Magics {
+    magics: [Magic; 64],
+    attacks: Vec<Bitboard>,
+}
+
+impl Magics {
+    pub fn rooks() -> Self {
+        Self::new(&ORTHOGONALS, &ROOK_MAGICS)
+    }
+
+    pub fn bishops() -> Self {
+        Self::new(&DIAGONALS, &BISHOP_MAGICS)
+    }
+
+    // PEXT needs no magics, only the masks
+    #[cfg_attr(feature = "pext", allow(unused_variables))]
+    fn new(directions: &[(i8, i8)], magics: &[u64; 64]) -> Self {
+        let mut masks = Magics {
+            magics: [Magic {
+                mask: Bitboard::new(),
+                #[cfg(not(feature = "pext"))]
+                magic: 0,
+                #[cfg(not(feature = "pext"))]
+                shift: 0,
+                offset: 0,
+            }; 64],
+            attacks: vec![],
+        };
+
+        for square in 0..64u8 {
+            let rays = Self::rays(square, directions);
+
+            // The last square of a ray is attacked whatever stands on it, so it's left out of the mask
+            let mask = rays
+                .iter()
+                .flat_map(|ray| &ray[..ray.len().saturating_sub(1)])
+                .fold(0, |mask, target| mask | target);
+            let bits = mask.count_ones();
+
+            let magic = Magic {
+                mask: Bitboard::from(mask),
+                #[cfg(not(feature = "pext"))]
+                magic: magics[square as usize],
+                #[cfg(not(feature = "pext"))]
+                shift: 64 - bits as u8,
+                offset: masks.attacks.len(),
+            };
+
+            masks.magics[square as usize] = magic;
+            masks
+                .attacks
+                .resize(magic.offset + (1 << bits), Bitboard::new());
+
+            // https://www.chessprogramming.org/Traversing_Subsets_of_a_Set#All_Subsets_of_any_Set
+            let mut occupancy = 0u64;
+            loop {
+                let index = magic.index(Bitboard::from(occupancy));
+                let attacks = Bitboard::from(Self::slide(&rays, occupancy));
+
+                // Occupancies may only share a slot when they attack the same squares, otherwise the
+                // magic is broken. Sliders always attack something, so an empty slot is unfilled.
+                // This only runs once while the tables are built, so release builds check it too
+                assert!(
+                    masks.attacks[index].is_none() || masks.attacks[index] == attacks,
+                    "Magic for square {square} maps different attacks to slot {index}"
+                );
+                masks.attacks[index] = attacks;
+
+                occupancy = occupancy.wrapping_sub(mask) & mask;
+                if occupancy == 0 {
+                    break;
+                }
+            }
+        }
+
+        masks
+    }
+
+    pub fn get(&self, square: u8, occupancy: Bitboard) -> Bitboard {
+        self.attacks[self.magics[square as usize].index(occupancy)]
+    }
+
+    // Squares in each direction from the square, nearest first, walked for every occupancy
+    fn rays(square: u8, directions: &[(i8, i8)]) -> Vec<Vec<u64>> {
+        directions
+            .iter()
+            .map(|&direction| {
+                let mut ray = vec![];
+                let mut current = square;
+
+                while let Some(next) = Self::offset(current, direction) {
+                    ray.push(1 << next);
+                    current = next;
+                }
+
+                ray
+            })
+            .collect()
+    }
+
+    fn slide(rays: &[Vec<u64>], occupancy: u64) -> u64 {
+        let mut attacks = 0;
+
+        for ray in rays {
+            for &target in ray {
+                attacks |= target;
+
+                if occupancy & target != 0 {
+                    break;
+                }
+            }
+        }
+
+        attacks
+    }
+
+    fn offset(square: u8, (files, ranks): (i8, i8)) -> Option<u8> {
+        let file = (square & 7) as i8 + files;
+        let rank = (square >> 3) as i8 + ranks;
+
+        ((0..8).contains(&file) && (0..8).contains(&rank)).then_some((rank * 8 + file) as u8)
+    }
+}
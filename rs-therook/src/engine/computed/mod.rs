@@ -1,18 +1,27 @@
 mod _xray_attacks;
 mod attacks;
 mod betweens;
+mod lines;
+mod magics;
 mod rays;
 
 use super::*;
+use std::sync::LazyLock;
 
-use attacks::*;
-use betweens::*;
-use rays::*;
+pub use attacks::*;
+pub use betweens::*;
+pub use lines::*;
+pub use magics::*;
+pub use rays::*;
+
+// Built the first time a board needs it and shared by every board and thread after that
+pub static COMPUTED: LazyLock<Computed> = LazyLock::new(Computed::new);
 
 pub struct Computed {
     pub rays: Rays,
     pub attacks: Attacks,
     pub betweens: Betweens,
+    pub lines: Lines,
 }
 
 impl Computed {
@@ -21,6 +30,13 @@ impl Computed {
             rays: Rays::new(),
             attacks: Attacks::new(),
             betweens: Betweens::new(),
+            lines: Lines::new(),
         }
     }
 }
+
+impl Default for Computed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -42,3 +42,9 @@ impl Rays {
         masks
     }
 }
+
+impl Default for Rays {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+impl HandCrafted {
+    pub const PAWN_SHIELD: TaperedScore = TaperedScore::new(12, 0);
+    pub const KING_ZONE_ATTACK: TaperedScore = TaperedScore::new(-8, 0);
+
+    // https://www.chessprogramming.org/King_Safety
+    pub(super) fn king_safety(&self, board: &Board, color: PieceColor) -> TaperedScore {
+        let mut score = TaperedScore::default();
+
+        let enemy = color.opposite();
+        let king = board.pieces[color | PieceType::King];
+        let Ok(king_square) = u8::try_from(king) else {
+            return score;
+        };
+
+        let occupancy = board.colors[color] | board.colors[enemy];
+        let zone = COMPUTED
+            .attacks
+            .get(color, PieceType::King, king_square, occupancy)
+            | king;
+
+        // Friendly pawns on the two ranks in front of the king
+        let files = king | king.east() | king.west();
+        let shield = match color {
+            PieceColor::White => files.north() | files.north().north(),
+            PieceColor::Black => files.south() | files.south().south(),
+        };
+        score +=
+            Self::PAWN_SHIELD * (shield & board.pieces[color | PieceType::Pawn]).count() as i32;
+
+        // Weighted count of enemy piece attacks landing inside the king zone
+        let mut units = 0;
+        for r#type in [
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+        ] {
+            let weight = match r#type {
+                PieceType::Queen => 4,
+                PieceType::Rook => 3,
+                _ => 2,
+            };
+
+            for square in board.pieces[enemy | r#type] {
+                let attacks = COMPUTED.attacks.get(enemy, r#type, square, occupancy);
+                units += weight * (attacks & zone).count() as i32;
+            }
+        }
+
+        score += Self::KING_ZONE_ATTACK * units;
+
+        score
+    }
+}
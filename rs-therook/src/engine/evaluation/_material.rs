@@ -0,0 +1,25 @@
+use super::*;
+
+// https://www.chessprogramming.org/PeSTO%27s_Evaluation_Function
+impl HandCrafted {
+    pub(super) fn material(&self, board: &Board, color: PieceColor) -> TaperedScore {
+        let mut score = TaperedScore::default();
+
+        for r#type in PieceType::ALL {
+            score += Self::get_material(r#type) * board.pieces[color | r#type].count() as i32;
+        }
+
+        score
+    }
+
+    pub fn get_material(r#type: PieceType) -> TaperedScore {
+        match r#type {
+            PieceType::King => TaperedScore::new(0, 0),
+            PieceType::Queen => TaperedScore::new(1025, 936),
+            PieceType::Rook => TaperedScore::new(477, 512),
+            PieceType::Bishop => TaperedScore::new(365, 297),
+            PieceType::Knight => TaperedScore::new(337, 281),
+            PieceType::Pawn => TaperedScore::new(82, 94),
+        }
+    }
+}
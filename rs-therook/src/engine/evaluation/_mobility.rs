@@ -0,0 +1,47 @@
+use super::*;
+
+impl HandCrafted {
+    // https://www.chessprogramming.org/Mobility
+    pub(super) fn mobility(&self, board: &Board, color: PieceColor) -> TaperedScore {
+        let mut score = TaperedScore::default();
+
+        let enemy = color.opposite();
+        let occupancy = board.colors[color] | board.colors[enemy];
+
+        // Squares attacked by enemy pawns are not worth counting as safe destinations
+        let mut enemy_pawn_attacks = Bitboard::new();
+        for square in board.pieces[enemy | PieceType::Pawn] {
+            enemy_pawn_attacks |= COMPUTED
+                .attacks
+                .get(enemy, PieceType::Pawn, square, occupancy);
+        }
+
+        let available = !board.colors[color] & !enemy_pawn_attacks;
+
+        for r#type in [
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+        ] {
+            let weight = Self::get_mobility_weight(r#type);
+
+            for square in board.pieces[color | r#type] {
+                let attacks = COMPUTED.attacks.get(color, r#type, square, occupancy);
+                score += weight * (attacks & available).count() as i32;
+            }
+        }
+
+        score
+    }
+
+    fn get_mobility_weight(r#type: PieceType) -> TaperedScore {
+        match r#type {
+            PieceType::Queen => TaperedScore::new(1, 2),
+            PieceType::Rook => TaperedScore::new(2, 4),
+            PieceType::Bishop => TaperedScore::new(4, 4),
+            PieceType::Knight => TaperedScore::new(4, 4),
+            _ => TaperedScore::default(),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+impl HandCrafted {
+    pub const DOUBLED: TaperedScore = TaperedScore::new(-10, -25);
+    pub const ISOLATED: TaperedScore = TaperedScore::new(-12, -15);
+
+    // Indexed by the rank of the pawn relative to its own side
+    pub const PASSED: [TaperedScore; 8] = [
+        TaperedScore::new(0, 0),
+        TaperedScore::new(2, 8),
+        TaperedScore::new(5, 15),
+        TaperedScore::new(10, 30),
+        TaperedScore::new(20, 55),
+        TaperedScore::new(35, 90),
+        TaperedScore::new(55, 140),
+        TaperedScore::new(0, 0),
+    ];
+
+    // https://www.chessprogramming.org/Pawn_Structure
+    pub(super) fn pawns(&self, board: &Board, color: PieceColor) -> TaperedScore {
+        let mut score = TaperedScore::default();
+
+        let pawns = board.pieces[color | PieceType::Pawn];
+        let enemy_pawns = board.pieces[color.opposite() | PieceType::Pawn];
+
+        for file in 0..8u8 {
+            let count = (pawns & (FILE_A << file)).count() as i32;
+
+            if count > 1 {
+                score += Self::DOUBLED * (count - 1);
+            }
+        }
+
+        for square in pawns {
+            let file = FILE_A << (square & 7);
+            let adjacent_files = file.east() | file.west();
+
+            if (pawns & adjacent_files).is_none() {
+                score += Self::ISOLATED;
+            }
+
+            if (enemy_pawns & Self::get_front_span(color, square)).is_none() {
+                let rank = match color {
+                    PieceColor::White => square >> 3,
+                    PieceColor::Black => 7 - (square >> 3),
+                };
+
+                score += Self::PASSED[rank as usize];
+            }
+        }
+
+        score
+    }
+
+    // Squares in front of a pawn on its own and adjacent files, which enemy pawns could stop it from
+    pub(super) fn get_front_span(color: PieceColor, square: u8) -> Bitboard {
+        let file = FILE_A << (square & 7);
+        let files = file | file.east() | file.west();
+        let rank = square >> 3;
+
+        let ranks = match color {
+            PieceColor::White if rank == 7 => 0,
+            PieceColor::White => !0u64 << ((rank + 1) * 8),
+            PieceColor::Black => (1u64 << (rank * 8)) - 1,
+        };
+
+        files & ranks
+    }
+}
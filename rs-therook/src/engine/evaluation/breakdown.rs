@@ -0,0 +1,78 @@
+use super::*;
+
+#[derive(Clone, Copy)]
+pub struct EvaluationBreakdown {
+    pub turn: PieceColor,
+    pub phase: i32,
+
+    pub material: [TaperedScore; 2],
+    pub psqt: [TaperedScore; 2],
+    pub pawns: [TaperedScore; 2],
+    pub mobility: [TaperedScore; 2],
+    pub king_safety: [TaperedScore; 2],
+}
+
+impl EvaluationBreakdown {
+    pub fn get_terms(&self) -> [(&'static str, [TaperedScore; 2]); 5] {
+        [
+            ("Material", self.material),
+            ("PSQT", self.psqt),
+            ("Pawns", self.pawns),
+            ("Mobility", self.mobility),
+            ("King safety", self.king_safety),
+        ]
+    }
+
+    // Sum of every term from White's perspective
+    pub fn get_total(&self) -> TaperedScore {
+        self.get_terms()
+            .iter()
+            .fold(TaperedScore::default(), |acc, (_, term)| {
+                acc + term[PieceColor::White] - term[PieceColor::Black]
+            })
+    }
+
+    pub fn get_score(&self) -> Score {
+        let score = self.get_total().taper(self.phase);
+
+        Score::from(match self.turn {
+            PieceColor::White => score,
+            PieceColor::Black => -score,
+        })
+    }
+}
+
+impl std::fmt::Debug for EvaluationBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = String::new();
+
+        lines.push_str("     Term    |    White    |    Black    |    Total\n");
+        lines.push_str("             |   MG    EG  |   MG    EG  |   MG    EG\n");
+        lines.push_str("-------------+-------------+-------------+------------\n");
+
+        for (name, term) in self.get_terms() {
+            let white = term[PieceColor::White];
+            let black = term[PieceColor::Black];
+            let total = white - black;
+
+            lines.push_str(&format!(
+                "{name:>12} | {:>5} {:>5} | {:>5} {:>5} | {:>5} {:>5}\n",
+                white.mg, white.eg, black.mg, black.eg, total.mg, total.eg
+            ));
+        }
+
+        let total = self.get_total();
+        lines.push_str("-------------+-------------+-------------+------------\n");
+        lines.push_str(&format!(
+            "{:>12} | {:>11} | {:>11} | {:>5} {:>5}\n",
+            "Total", "", "", total.mg, total.eg
+        ));
+        lines.push_str(&format!(
+            "\nPhase: {}/{MAX_PHASE}, final evaluation: {:?} (side to move)\n",
+            self.phase,
+            self.get_score()
+        ));
+
+        write!(f, "{}", lines)
+    }
+}
@@ -0,0 +1,208 @@
+mod _king_safety;
+mod _material;
+mod _mobility;
+mod _pawns;
+mod _psqt;
+mod breakdown;
+mod tapered;
+
+use super::*;
+pub use breakdown::*;
+pub use tapered::*;
+
+pub const MAX_PHASE: i32 = 24;
+
+pub trait Evaluator {
+    // Static score of the position from the side to move's perspective
+    fn evaluate(&self, board: &Board) -> Score;
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct HandCrafted;
+
+impl HandCrafted {
+    pub fn breakdown(&self, board: &Board) -> EvaluationBreakdown {
+        let mut breakdown = EvaluationBreakdown {
+            turn: board.turn,
+            phase: Self::get_phase(board),
+
+            material: [TaperedScore::default(); 2],
+            psqt: [TaperedScore::default(); 2],
+            pawns: [TaperedScore::default(); 2],
+            mobility: [TaperedScore::default(); 2],
+            king_safety: [TaperedScore::default(); 2],
+        };
+
+        for color in PieceColor::ALL {
+            breakdown.material[color] = self.material(board, color);
+            breakdown.psqt[color] = self.psqt(board, color);
+            breakdown.pawns[color] = self.pawns(board, color);
+            breakdown.mobility[color] = self.mobility(board, color);
+            breakdown.king_safety[color] = self.king_safety(board, color);
+        }
+
+        breakdown
+    }
+
+    // 24 with all minor and major pieces on the board, down to 0 with only kings and pawns
+    fn get_phase(board: &Board) -> i32 {
+        let mut phase = 0;
+
+        for color in PieceColor::ALL {
+            phase += board.pieces[color | PieceType::Knight].count() as i32;
+            phase += board.pieces[color | PieceType::Bishop].count() as i32;
+            phase += board.pieces[color | PieceType::Rook].count() as i32 * 2;
+            phase += board.pieces[color | PieceType::Queen].count() as i32 * 4;
+        }
+
+        phase.min(MAX_PHASE)
+    }
+}
+
+impl Evaluator for HandCrafted {
+    fn evaluate(&self, board: &Board) -> Score {
+        self.breakdown(board).get_score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Flips the board vertically and swaps the colors of every piece and the side to move
+    fn mirror(fen: &str) -> String {
+        let fields = fen.split(' ').collect::<Vec<_>>();
+
+        let swap_case = |c: char| {
+            if c.is_uppercase() {
+                c.to_ascii_lowercase()
+            } else {
+                c.to_ascii_uppercase()
+            }
+        };
+
+        let placement = fields[0]
+            .split('/')
+            .rev()
+            .map(|rank| rank.chars().map(swap_case).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("/");
+        let turn = if fields[1] == "w" { "b" } else { "w" };
+
+        format!("{placement} {turn} - - 0 1")
+    }
+
+    fn evaluate(fen: &str) -> Score {
+        HandCrafted.evaluate(&Board::from_fen(fen))
+    }
+
+    #[test]
+    fn initial_is_balanced() {
+        let board = Board::initial();
+        let breakdown = HandCrafted.breakdown(&board);
+
+        assert_eq!(breakdown.phase, MAX_PHASE);
+        assert_eq!(breakdown.get_total(), TaperedScore::default());
+        assert_eq!(HandCrafted.evaluate(&board), Score::ZERO);
+    }
+
+    #[test]
+    fn symmetric() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w - - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w - - 0 1",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 1",
+        ] {
+            assert_eq!(evaluate(fen), evaluate(&mirror(fen)), "{fen}");
+        }
+    }
+
+    #[test]
+    fn side_to_move_perspective() {
+        let white = evaluate("4k3/8/8/8/8/8/8/3QK3 w - - 0 1");
+        let black = evaluate("4k3/8/8/8/8/8/8/3QK3 b - - 0 1");
+
+        assert!(white > Score::from(800));
+        assert_eq!(white, -black);
+    }
+
+    #[test]
+    fn phase() {
+        let board = Board::from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1");
+        assert_eq!(HandCrafted.breakdown(&board).phase, 0);
+
+        let board = Board::from_fen("3qk3/8/8/8/8/8/8/1N2K1R1 w - - 0 1");
+        assert_eq!(HandCrafted.breakdown(&board).phase, 7);
+    }
+
+    mod pawns {
+        use super::*;
+
+        fn pawns(fen: &str, color: PieceColor) -> TaperedScore {
+            HandCrafted.pawns(&Board::from_fen(fen), color)
+        }
+
+        #[test]
+        fn doubled_and_isolated() {
+            // Doubled and isolated on the A file, blocked by the black pawn so neither is passed
+            assert_eq!(
+                pawns("4k3/p7/8/8/8/P7/P7/4K3 w - - 0 1", PieceColor::White),
+                HandCrafted::DOUBLED + HandCrafted::ISOLATED * 2
+            );
+        }
+
+        #[test]
+        fn passed() {
+            assert_eq!(
+                pawns("4k3/8/1P6/8/8/8/8/4K3 w - - 0 1", PieceColor::White),
+                HandCrafted::ISOLATED + HandCrafted::PASSED[5]
+            );
+
+            // The black pawn on an adjacent file can still stop it
+            assert_eq!(
+                pawns("4k3/2p5/1P6/8/8/8/8/4K3 w - - 0 1", PieceColor::White),
+                HandCrafted::ISOLATED
+            );
+
+            assert_eq!(
+                pawns("4k3/8/8/8/8/6p1/8/4K3 b - - 0 1", PieceColor::Black),
+                HandCrafted::ISOLATED + HandCrafted::PASSED[5]
+            );
+        }
+    }
+
+    #[test]
+    fn mobility() {
+        let board = Board::from_fen("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1");
+
+        assert_eq!(
+            HandCrafted.mobility(&board, PieceColor::White),
+            TaperedScore::new(4, 4) * 8
+        );
+
+        // Squares attacked by enemy pawns don't count
+        let board = Board::from_fen("4k3/3p4/8/8/3N4/8/8/4K3 w - - 0 1");
+        assert_eq!(
+            HandCrafted.mobility(&board, PieceColor::White),
+            TaperedScore::new(4, 4) * 6
+        );
+    }
+
+    #[test]
+    fn king_safety() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1");
+
+        assert_eq!(
+            HandCrafted.king_safety(&board, PieceColor::White),
+            HandCrafted::PAWN_SHIELD * 3
+        );
+
+        // The queen hits both F1 and G1 in the king zone
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/q5K1 w - - 0 1");
+        assert_eq!(
+            HandCrafted.king_safety(&board, PieceColor::White),
+            HandCrafted::PAWN_SHIELD * 3 + HandCrafted::KING_ZONE_ATTACK * 8
+        );
+    }
+}
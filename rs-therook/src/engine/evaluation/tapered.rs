@@ -0,0 +1,55 @@
+use super::*;
+use std::ops::*;
+
+// A pair of midgame and endgame values, blended by the game phase at the end of evaluation
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TaperedScore {
+    pub mg: i32,
+    pub eg: i32,
+}
+
+impl TaperedScore {
+    pub const fn new(mg: i32, eg: i32) -> Self {
+        TaperedScore { mg, eg }
+    }
+
+    // https://www.chessprogramming.org/Tapered_Eval
+    pub fn taper(&self, phase: i32) -> i32 {
+        (self.mg * phase + self.eg * (MAX_PHASE - phase)) / MAX_PHASE
+    }
+}
+
+impl Add<TaperedScore> for TaperedScore {
+    type Output = Self;
+    fn add(self, rhs: TaperedScore) -> Self::Output {
+        TaperedScore::new(self.mg + rhs.mg, self.eg + rhs.eg)
+    }
+}
+
+impl Sub<TaperedScore> for TaperedScore {
+    type Output = Self;
+    fn sub(self, rhs: TaperedScore) -> Self::Output {
+        TaperedScore::new(self.mg - rhs.mg, self.eg - rhs.eg)
+    }
+}
+
+impl Mul<i32> for TaperedScore {
+    type Output = Self;
+    fn mul(self, rhs: i32) -> Self::Output {
+        TaperedScore::new(self.mg * rhs, self.eg * rhs)
+    }
+}
+
+impl AddAssign<TaperedScore> for TaperedScore {
+    fn add_assign(&mut self, rhs: TaperedScore) {
+        self.mg += rhs.mg;
+        self.eg += rhs.eg;
+    }
+}
+
+impl SubAssign<TaperedScore> for TaperedScore {
+    fn sub_assign(&mut self, rhs: TaperedScore) {
+        self.mg -= rhs.mg;
+        self.eg -= rhs.eg;
+    }
+}
@@ -1,13 +1,33 @@
 mod bitboard;
 mod board;
 mod computed;
+mod evaluation;
 mod r#move;
-mod piece;
 mod perft;
+mod piece;
+// Random games shared by the property tests
+#[cfg(test)]
+mod playout;
+mod reference;
+mod score;
+mod search;
+mod transposition;
+mod zobrist;
 
-pub use super::*;
+// Only the tests still place pieces with the square! macro
+#[cfg(test)]
+use super::*;
 pub use bitboard::*;
 pub use board::*;
 pub use computed::*;
+pub use evaluation::*;
 pub use r#move::*;
+pub use perft::*;
 pub use piece::*;
+#[cfg(test)]
+pub use playout::*;
+pub use reference::*;
+pub use score::*;
+pub use search::*;
+pub use transposition::*;
+pub use zobrist::*;
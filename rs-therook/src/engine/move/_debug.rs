@@ -2,18 +2,27 @@ use super::*;
 
 impl std::fmt::Debug for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
+        write!(f, "{}", self.to_uci(false))
+    }
+}
+
+impl Move {
+    // Castling is written as the king moving two squares, or as it capturing its own rook in Chess960
+    pub fn to_uci(&self, chess960: bool) -> String {
+        let end = match self.get_flag() {
+            MoveFlag::Castle if !chess960 => self.get_castling_squares().0,
+            _ => self.get_end(),
+        };
+
+        format!(
             "{}{}{}",
             Self::format_square(self.get_start()),
-            Self::format_square(self.get_end()),
+            Self::format_square(end),
             Self::format_promotion(self.get_promote_piece_type())
         )
     }
-}
 
-impl Move {
-    fn format_square(square: u8) -> String {
+    pub fn format_square(square: u8) -> String {
         format!(
             "{}{}",
             ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'][square as usize & 7],
@@ -21,6 +30,18 @@ impl Move {
         )
     }
 
+    pub fn parse_square(text: &str) -> Option<u8> {
+        let mut chars = text.chars();
+        let file = chars.next()?;
+        let rank = chars.next()?;
+
+        if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return None;
+        }
+
+        Some((rank as u8 - b'1') * 8 + (file as u8 - b'a'))
+    }
+
     fn format_promotion(promotion_piece_type: Option<PieceType>) -> String {
         if let Some(piece_type) = promotion_piece_type {
             match piece_type {
@@ -0,0 +1,143 @@
+use super::*;
+use std::ops::{Deref, DerefMut};
+
+// Legal moves generated into a fixed array on the stack, so that move generation never allocates.
+// No position has more than 218 legal moves
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MoveList::CAPACITY],
+    len: usize,
+}
+
+impl MoveList {
+    pub const CAPACITY: usize = 256;
+
+    pub fn new() -> Self {
+        MoveList {
+            moves: [Move::from(0); Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, r#move: Move) {
+        self.moves[self.len] = r#move;
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    // Keeps the order of the moves that stay, like Vec::retain
+    pub fn retain(&mut self, mut keep: impl FnMut(&Move) -> bool) {
+        let mut len = 0;
+
+        for index in 0..self.len {
+            if keep(&self.moves[index]) {
+                self.moves[len] = self.moves[index];
+                len += 1;
+            }
+        }
+
+        self.len = len;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = std::iter::Take<std::array::IntoIter<Move, { MoveList::CAPACITY }>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.into_iter().take(self.len)
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialEq<Vec<Move>> for MoveList {
+    fn eq(&self, other: &Vec<Move>) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for MoveList {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_retain_clear() {
+        let mut moves = MoveList::new();
+        assert!(moves.is_empty());
+
+        moves.push(Move::new(square!(E2), square!(E4), MoveFlag::PawnDash));
+        moves.push(Move::new(square!(G1), square!(F3), MoveFlag::None));
+
+        assert_eq!(moves.len(), 2);
+        assert_eq!(
+            moves[1],
+            Move::new(square!(G1), square!(F3), MoveFlag::None)
+        );
+        assert_eq!(
+            moves.clone().into_iter().collect::<Vec<_>>(),
+            moves.iter().copied().collect::<Vec<_>>()
+        );
+
+        moves.retain(|r#move| r#move.get_flag() == MoveFlag::None);
+        assert_eq!(
+            moves,
+            vec![Move::new(square!(G1), square!(F3), MoveFlag::None)]
+        );
+
+        moves.clear();
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn holds_most_moves() {
+        // 218 legal moves, the most any position has
+        let board = Board::from_fen("R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1");
+
+        assert_eq!(board.calculate_moves().len(), 218);
+    }
+}
@@ -1,8 +1,10 @@
 mod _debug;
 mod flag;
+mod list;
 
 use super::*;
 pub use flag::*;
+pub use list::*;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Move(u16);
@@ -37,4 +39,31 @@ impl Move {
             _ => None,
         }
     }
+
+    // Castling moves are stored as the king capturing its own rook, so that they stay unambiguous
+    // in Chess960. Both pieces land on the same squares as in standard chess
+    // https://www.chessprogramming.org/Chess960#Castling
+    pub fn get_castling_squares(&self) -> (u8, u8) {
+        let king = self.get_start();
+        let rook = self.get_end();
+        let row = king & 56;
+
+        if rook > king {
+            (row + 6, row + 5)
+        } else {
+            (row + 2, row + 3)
+        }
+    }
+}
+
+impl From<Move> for u16 {
+    fn from(r#move: Move) -> Self {
+        r#move.0
+    }
+}
+
+impl From<u16> for Move {
+    fn from(u16: u16) -> Self {
+        Move(u16)
+    }
 }
@@ -0,0 +1,119 @@
+use super::*;
+
+impl Board {
+    // Follows the first root move whose count disagrees with the oracle down the tree, until reaching
+    // the position where the legal moves differ. None when every count agrees
+    pub fn bisect(&self, oracle: &mut impl PerftOracle, depth: u8) -> Option<PerftBug> {
+        let mut board = self.clone();
+        let mut line = vec![];
+
+        for depth in (1..=depth).rev() {
+            let expected = oracle.divide(&board, depth);
+            let actual = board
+                .divide(depth)
+                .into_iter()
+                .map(|(r#move, count)| (r#move, r#move.to_uci(board.chess960), count))
+                .collect::<Vec<_>>();
+
+            let missing = expected
+                .iter()
+                .filter(|(r#move, _)| !actual.iter().any(|(_, other, _)| other == r#move))
+                .map(|(r#move, _)| r#move.clone())
+                .collect::<Vec<_>>();
+            let extra = actual
+                .iter()
+                .filter(|(_, r#move, _)| !expected.iter().any(|(other, _)| other == r#move))
+                .map(|(_, r#move, _)| r#move.clone())
+                .collect::<Vec<_>>();
+
+            if !missing.is_empty() || !extra.is_empty() {
+                return Some(PerftBug {
+                    fen: board.to_fen(),
+                    line,
+                    chess960: board.chess960,
+                    missing,
+                    extra,
+                });
+            }
+
+            // With the same moves at depth 1 every count is 1, so this only stops higher up
+            let (r#move, _, _) = actual.into_iter().find(|(_, uci, count)| {
+                expected
+                    .iter()
+                    .any(|(other, expected)| other == uci && expected != count)
+            })?;
+
+            board.make_move(r#move);
+            line.push(r#move);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An oracle with a bug of its own, it never castles
+    fn divide_without_castling(board: &Board, depth: u8) -> Vec<(String, u64)> {
+        fn perft(board: &mut Board, depth: u8) -> u64 {
+            if depth == 0 {
+                return 1;
+            }
+
+            let mut count = 0;
+
+            for r#move in board.calculate_moves() {
+                if r#move.get_flag() != MoveFlag::Castle {
+                    board.make_move(r#move);
+                    count += perft(board, depth - 1);
+                    board.undo_move(r#move);
+                }
+            }
+
+            count
+        }
+
+        let mut board = board.clone();
+
+        board
+            .calculate_moves()
+            .into_iter()
+            .filter(|r#move| r#move.get_flag() != MoveFlag::Castle)
+            .map(|r#move| {
+                board.make_move(r#move);
+                let count = perft(&mut board, depth - 1);
+                board.undo_move(r#move);
+
+                (r#move.to_uci(false), count)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_difference() {
+        let board = Board::from_fen("r3k3/8/8/8/8/8/8/4K3 w q - 0 1");
+        let bug = board.bisect(&mut divide_without_castling, 3).unwrap();
+
+        assert_eq!(bug.line.len(), 1);
+        assert_eq!(bug.missing, Vec::<String>::new());
+        assert_eq!(bug.extra, vec!["e8c8"]);
+
+        let mut expected = board.clone();
+        expected.make_move(bug.line[0]);
+        assert_eq!(&expected, bug.fen.as_str());
+    }
+
+    #[test]
+    fn agrees() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 b Q - 0 1");
+
+        assert_eq!(board.bisect(&mut divide_without_castling, 1), None);
+        assert!(board.bisect(&mut divide_without_castling, 2).is_some());
+        assert_eq!(
+            Board::initial().bisect(&mut divide_without_castling, 3),
+            None
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+// The first position down a wrong perft count where the legal moves themselves differ
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PerftBug {
+    pub fen: String,
+    // Moves from the root position leading to this one
+    pub line: Vec<Move>,
+    // Castles in the line are only written king-takes-rook in Chess960
+    pub chess960: bool,
+    // Moves the oracle has but the board doesn't generate, and the other way around
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+impl std::fmt::Display for PerftBug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = self.line.iter().map(|r#move| r#move.to_uci(self.chess960));
+
+        writeln!(f, "FEN: {}", self.fen)?;
+        writeln!(f, "Line: {}", line.collect::<Vec<_>>().join(" "))?;
+        writeln!(f, "Missing: {}", self.missing.join(" "))?;
+        write!(f, "Extra: {}", self.extra.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_in_chess960() {
+        let castle = Move::new(square!(E1), square!(H1), MoveFlag::Castle);
+        let mut bug = PerftBug {
+            fen: "4k3/8/8/8/8/8/8/5RK1 b - - 1 1".into(),
+            line: vec![castle],
+            chess960: false,
+            missing: vec![],
+            extra: vec!["e8d8".into()],
+        };
+
+        assert!(bug.to_string().contains("Line: e1g1\n"));
+
+        bug.chess960 = true;
+        assert!(bug.to_string().contains("Line: e1h1\n"));
+    }
+}
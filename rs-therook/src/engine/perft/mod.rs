@@ -0,0 +1,222 @@
+mod _bisect;
+mod bug;
+mod oracle;
+mod positions;
+
+use super::*;
+pub use bug::*;
+pub use oracle::*;
+pub use positions::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// https://www.chessprogramming.org/Perft
+impl Board {
+    // Number of leaf nodes of the legal move tree, depth 0 being the position itself
+    pub fn perft(&mut self, depth: u8) -> u64 {
+        match depth {
+            0 => return 1,
+            // Bulk counting, the leaves are exactly the legal moves so they don't need to be made
+            // https://www.chessprogramming.org/Perft#Bulk-counting
+            1 => return self.calculate_moves().len() as u64,
+            _ => {}
+        }
+
+        let mut count = 0;
+
+        for r#move in self.calculate_moves() {
+            self.make_move(r#move);
+            count += self.perft(depth - 1);
+            self.undo_move(r#move);
+        }
+
+        count
+    }
+
+    // Transpositions reach the same position with the same depth left, so their counts can be reused
+    // https://www.chessprogramming.org/Perft#Hashing
+    pub fn perft_hashed(&mut self, depth: u8, table: &TranspositionTable) -> u64 {
+        match depth {
+            0 => return 1,
+            // Cheaper to count again than to give the leaves their own table slots
+            1 => return self.calculate_moves().len() as u64,
+            _ => {}
+        }
+
+        let key = self.get_key();
+        if let Some(count) = table.probe_perft(key, depth) {
+            return count;
+        }
+
+        let mut count = 0;
+
+        for r#move in self.calculate_moves() {
+            self.make_move(r#move);
+            count += self.perft_hashed(depth - 1, table);
+            self.undo_move(r#move);
+        }
+
+        table.store_perft(key, depth, count);
+        count
+    }
+
+    // Perft split by root move, which narrows a wrong count down to the move responsible for it
+    // https://www.chessprogramming.org/Perft#Divide
+    pub fn divide(&mut self, depth: u8) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return vec![];
+        }
+
+        let mut counts = vec![];
+
+        for r#move in self.calculate_moves() {
+            self.make_move(r#move);
+            counts.push((r#move, self.perft(depth - 1)));
+            self.undo_move(r#move);
+        }
+
+        counts
+    }
+
+    // Divide with the root moves split across threads, each working on its own copy of the board.
+    // Moves are handed out one at a time so that threads drawing small subtrees pick up more of them
+    pub fn divide_parallel(
+        &self,
+        depth: u8,
+        threads: usize,
+        table: Option<&TranspositionTable>,
+    ) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return vec![];
+        }
+
+        let moves = self.calculate_moves();
+        let mut counts = vec![0; moves.len()];
+        let next = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            let handles = (0..threads.max(1))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut board = self.clone();
+                        let mut counts = vec![];
+
+                        while let Some(&r#move) = moves.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            board.make_move(r#move);
+                            let count = match table {
+                                Some(table) => board.perft_hashed(depth - 1, table),
+                                None => board.perft(depth - 1),
+                            };
+                            board.undo_move(r#move);
+
+                            counts.push((r#move, count));
+                        }
+
+                        counts
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                for (r#move, count) in handle.join().unwrap() {
+                    let index = moves.iter().position(|&other| other == r#move).unwrap();
+                    counts[index] = count;
+                }
+            }
+        });
+
+        moves.into_iter().zip(counts).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Keeps the debug build test run short, deeper counts are left to the perft benchmarks
+    const MAX_NODES: u64 = 1_000_000;
+
+    #[test]
+    fn perft_positions() {
+        for position in PERFT_POSITIONS {
+            let mut board = Board::from_fen(position.fen);
+            let fen = board.to_fen();
+
+            for (depth, expected) in position.get_depths(MAX_NODES) {
+                assert_eq!(
+                    board.perft(depth),
+                    expected,
+                    "{} depth {depth}",
+                    position.name
+                );
+            }
+
+            assert_eq!(board.to_fen(), fen, "{}", position.name);
+        }
+    }
+
+    #[test]
+    fn perft_hashed() {
+        let table = TranspositionTable::new(16);
+
+        for position in PERFT_POSITIONS {
+            let mut board = Board::from_fen(position.fen);
+            let (depth, expected) = position.get_depths(100_000).last().unwrap();
+
+            // A second run is answered almost entirely from the table and must agree
+            assert_eq!(
+                board.perft_hashed(depth, &table),
+                expected,
+                "{}",
+                position.name
+            );
+            assert_eq!(
+                board.perft_hashed(depth, &table),
+                expected,
+                "{}",
+                position.name
+            );
+        }
+    }
+
+    #[test]
+    fn divide() {
+        let mut board = Board::initial();
+        let counts = board.divide(3);
+
+        assert_eq!(counts.len(), 20);
+        assert_eq!(counts.iter().map(|(_, count)| count).sum::<u64>(), 8_902);
+        assert!(counts.contains(&(Move::new(square!(E2), square!(E4), MoveFlag::PawnDash), 600)));
+        assert!(board.divide(0).is_empty());
+    }
+
+    #[test]
+    fn divide_parallel() {
+        let table = TranspositionTable::new(16);
+
+        for position in PERFT_POSITIONS {
+            let mut board = Board::from_fen(position.fen);
+            let (depth, _) = position.get_depths(100_000).last().unwrap();
+            let expected = board.divide(depth);
+
+            assert_eq!(
+                board.divide_parallel(depth, 3, None),
+                expected,
+                "{}",
+                position.name
+            );
+            assert_eq!(
+                board.divide_parallel(depth, 2, Some(&table)),
+                expected,
+                "{}",
+                position.name
+            );
+        }
+    }
+
+    #[test]
+    fn get_depths() {
+        let depths = PERFT_POSITIONS[0].get_depths(10_000).collect::<Vec<_>>();
+        assert_eq!(depths, vec![(1, 20), (2, 400), (3, 8_902)]);
+    }
+}
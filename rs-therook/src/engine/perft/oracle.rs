@@ -0,0 +1,13 @@
+use super::*;
+
+// Anything that knows the correct divide of a position, with moves written in UCI notation so that
+// external engines can answer too
+pub trait PerftOracle {
+    fn divide(&mut self, board: &Board, depth: u8) -> Vec<(String, u64)>;
+}
+
+impl<F: FnMut(&Board, u8) -> Vec<(String, u64)>> PerftOracle for F {
+    fn divide(&mut self, board: &Board, depth: u8) -> Vec<(String, u64)> {
+        self(board, depth)
+    }
+}
@@ -0,0 +1,104 @@
+// Published node counts, counts[0] being depth 1
+// https://www.chessprogramming.org/Perft_Results
+// https://www.chessprogramming.org/Chess960_Perft_Results
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PerftPosition {
+    pub name: &'static str,
+    pub fen: &'static str,
+    pub counts: &'static [u64],
+}
+
+impl PerftPosition {
+    // Depths whose expected count stays within the given number of nodes
+    pub fn get_depths(&self, max_nodes: u64) -> impl Iterator<Item = (u8, u64)> + '_ {
+        (1..)
+            .zip(self.counts.iter().copied())
+            .take_while(move |&(_, count)| count <= max_nodes)
+    }
+}
+
+pub const PERFT_POSITIONS: [PerftPosition; 11] = [
+    PerftPosition {
+        name: "Initial position",
+        fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        counts: &[
+            20,
+            400,
+            8_902,
+            197_281,
+            4_865_609,
+            119_060_324,
+            3_195_901_860,
+            84_998_978_956,
+        ],
+    },
+    PerftPosition {
+        name: "Kiwipete",
+        fen: "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        counts: &[48, 2_039, 97_862, 4_085_603, 193_690_690, 8_031_647_685],
+    },
+    PerftPosition {
+        name: "Position 3",
+        fen: "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        counts: &[
+            14,
+            191,
+            2_812,
+            43_238,
+            674_624,
+            11_030_083,
+            178_633_661,
+            3_009_794_393,
+        ],
+    },
+    PerftPosition {
+        name: "Position 4",
+        fen: "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        counts: &[6, 264, 9_467, 422_333, 15_833_292, 706_045_033],
+    },
+    PerftPosition {
+        name: "Position 4 mirrored",
+        fen: "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        counts: &[6, 264, 9_467, 422_333, 15_833_292, 706_045_033],
+    },
+    PerftPosition {
+        name: "Position 5",
+        fen: "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        counts: &[44, 1_486, 62_379, 2_103_487, 89_941_194],
+    },
+    PerftPosition {
+        name: "Position 6",
+        fen: "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        counts: &[
+            46,
+            2_079,
+            89_890,
+            3_894_594,
+            164_075_551,
+            6_923_051_137,
+            287_188_994_746,
+            11_923_589_843_526,
+            490_154_852_788_714,
+        ],
+    },
+    PerftPosition {
+        name: "Chess960 position 1",
+        fen: "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        counts: &[21, 528, 12_189, 326_672, 8_146_062],
+    },
+    PerftPosition {
+        name: "Chess960 position 2",
+        fen: "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        counts: &[21, 807, 18_002, 667_366, 16_253_601],
+    },
+    PerftPosition {
+        name: "Chess960 position 3",
+        fen: "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+        counts: &[20, 479, 10_471, 273_318, 6_417_013],
+    },
+    PerftPosition {
+        name: "Chess960 position 4",
+        fen: "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+        counts: &[22, 593, 13_440, 382_958, 9_183_776],
+    },
+];
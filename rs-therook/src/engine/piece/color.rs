@@ -4,7 +4,7 @@ const WHITE: u8 = 0;
 const BLACK: u8 = 1;
 
 #[repr(u8)]
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PieceColor {
     White = WHITE,
     Black = BLACK,
@@ -0,0 +1,34 @@
+use super::*;
+use proptest::prelude::*;
+
+// The perft positions between them cover castling, en passant, promotions and checks
+pub fn perft_board() -> impl Strategy<Value = Board> {
+    (0..PERFT_POSITIONS.len()).prop_map(|position| Board::from_fen(PERFT_POSITIONS[position].fen))
+}
+
+// Each choice picks a legal move by index, so any list of them is a game from any position
+pub fn choices(plies: usize) -> impl Strategy<Value = Vec<usize>> {
+    prop::collection::vec(any::<usize>(), 0..plies)
+}
+
+// Plays the choices out, showing check every position on the way with its legal moves and the one
+// about to be made, which is None once the game is over
+pub fn playout<E>(
+    board: &mut Board,
+    choices: &[usize],
+    mut check: impl FnMut(&mut Board, &MoveList, Option<Move>) -> Result<(), E>,
+) -> Result<(), E> {
+    for choice in choices {
+        let moves = board.calculate_moves();
+        let r#move = (!moves.is_empty()).then(|| moves[choice % moves.len()]);
+
+        check(board, &moves, r#move)?;
+
+        match r#move {
+            Some(r#move) => board.make_move(r#move),
+            None => break,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,358 @@
+use super::*;
+
+// A deliberately naive legal move generator to test calculate_moves against. Pieces are walked over
+// the mailbox with file and rank offsets, without any of the precomputed tables, attack bitboards or
+// pin lines, and every pseudo-legal move is kept only if the king isn't attacked after making it
+// https://www.chessprogramming.org/Mailbox
+// https://www.chessprogramming.org/Pseudo-Legal_Move
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+const ORTHOGONAL_OFFSETS: [(i8, i8); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const DIAGONAL_OFFSETS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+const PROMOTIONS: [MoveFlag; 4] = [
+    MoveFlag::PromoteQueen,
+    MoveFlag::PromoteRook,
+    MoveFlag::PromoteBishop,
+    MoveFlag::PromoteKnight,
+];
+
+// Square reached by moving the given number of files and ranks, None when it falls off the board
+fn offset(square: u8, (files, ranks): (i8, i8)) -> Option<u8> {
+    let file = (square % 8) as i8 + files;
+    let rank = (square / 8) as i8 + ranks;
+
+    ((0..8).contains(&file) && (0..8).contains(&rank)).then_some((rank * 8 + file) as u8)
+}
+
+fn get_pawn_direction(color: PieceColor) -> i8 {
+    match color {
+        PieceColor::White => 1,
+        PieceColor::Black => -1,
+    }
+}
+
+fn find_king(squares: &[Option<Piece>; 64], color: PieceColor) -> Option<u8> {
+    (0..64).find(|&square| squares[square as usize] == Some(color | PieceType::King))
+}
+
+fn is_attacked(squares: &[Option<Piece>; 64], square: u8, color: PieceColor) -> bool {
+    let is = |square: Option<u8>, r#type: PieceType| {
+        square.is_some_and(|square| squares[square as usize] == Some(color | r#type))
+    };
+
+    // Pawns attack diagonally forward, so they are found diagonally backward from the square
+    let direction = get_pawn_direction(color);
+    if [-1, 1]
+        .into_iter()
+        .any(|file| is(offset(square, (file, -direction)), PieceType::Pawn))
+    {
+        return true;
+    }
+
+    if KNIGHT_OFFSETS
+        .into_iter()
+        .any(|knight| is(offset(square, knight), PieceType::Knight))
+    {
+        return true;
+    }
+
+    if KING_OFFSETS
+        .into_iter()
+        .any(|king| is(offset(square, king), PieceType::King))
+    {
+        return true;
+    }
+
+    let sliders = [
+        (ORTHOGONAL_OFFSETS, PieceType::Rook),
+        (DIAGONAL_OFFSETS, PieceType::Bishop),
+    ];
+
+    for (offsets, r#type) in sliders {
+        for direction in offsets {
+            let mut current = offset(square, direction);
+
+            while let Some(target) = current {
+                if let Some(piece) = squares[target as usize] {
+                    if piece == color | r#type || piece == color | PieceType::Queen {
+                        return true;
+                    }
+
+                    break;
+                }
+
+                current = offset(target, direction);
+            }
+        }
+    }
+
+    false
+}
+
+impl Board {
+    pub fn calculate_moves_reference(&self) -> Vec<Move> {
+        let color = self.turn;
+        let mut board = self.clone();
+
+        self.calculate_pseudo_legal_moves_reference()
+            .into_iter()
+            .filter(|&r#move| {
+                board.make_move(r#move);
+                let legal = find_king(&board.squares, color)
+                    .is_none_or(|king| !is_attacked(&board.squares, king, color.opposite()));
+                board.undo_move(r#move);
+
+                legal
+            })
+            .collect()
+    }
+
+    pub fn perft_reference(&mut self, depth: u8) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let mut count = 0;
+
+        for r#move in self.calculate_moves_reference() {
+            self.make_move(r#move);
+            count += self.perft_reference(depth - 1);
+            self.undo_move(r#move);
+        }
+
+        count
+    }
+
+    fn calculate_pseudo_legal_moves_reference(&self) -> Vec<Move> {
+        let mut moves = vec![];
+        let color = self.turn;
+
+        for square in 0..64 {
+            let Some(piece) = self.squares[square as usize] else {
+                continue;
+            };
+
+            if piece.get_color() != color {
+                continue;
+            }
+
+            match piece.get_type() {
+                PieceType::Pawn => self.add_pawn_moves_reference(square, &mut moves),
+                PieceType::Knight => self.add_steps_reference(square, &KNIGHT_OFFSETS, &mut moves),
+                PieceType::King => {
+                    self.add_steps_reference(square, &KING_OFFSETS, &mut moves);
+                    self.add_castles_reference(square, &mut moves);
+                }
+                PieceType::Bishop => {
+                    self.add_slides_reference(square, &DIAGONAL_OFFSETS, &mut moves)
+                }
+                PieceType::Rook => {
+                    self.add_slides_reference(square, &ORTHOGONAL_OFFSETS, &mut moves)
+                }
+                PieceType::Queen => {
+                    self.add_slides_reference(square, &ORTHOGONAL_OFFSETS, &mut moves);
+                    self.add_slides_reference(square, &DIAGONAL_OFFSETS, &mut moves);
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn is_enemy_reference(&self, square: u8) -> bool {
+        self.squares[square as usize].is_some_and(|piece| piece.get_color() != self.turn)
+    }
+
+    fn add_pawn_moves_reference(&self, square: u8, moves: &mut Vec<Move>) {
+        let direction = get_pawn_direction(self.turn);
+        let start_rank = if direction == 1 { 1 } else { 6 };
+        let promote_rank = if direction == 1 { 7 } else { 0 };
+
+        let mut add = |end: u8, flag: MoveFlag| {
+            if end / 8 == promote_rank {
+                moves.extend(PROMOTIONS.map(|flag| Move::new(square, end, flag)));
+            } else {
+                moves.push(Move::new(square, end, flag));
+            }
+        };
+
+        if let Some(end) = offset(square, (0, direction)) {
+            if self.squares[end as usize].is_none() {
+                add(end, MoveFlag::None);
+
+                if let Some(dash) = offset(end, (0, direction)) {
+                    if square / 8 == start_rank && self.squares[dash as usize].is_none() {
+                        add(dash, MoveFlag::PawnDash);
+                    }
+                }
+            }
+        }
+
+        for file in [-1, 1] {
+            let Some(end) = offset(square, (file, direction)) else {
+                continue;
+            };
+
+            if self.is_enemy_reference(end) {
+                add(end, MoveFlag::None);
+            } else if self.get_state().enpassant == Bitboard::from(end) {
+                add(end, MoveFlag::EnPassant);
+            }
+        }
+    }
+
+    fn add_steps_reference(&self, square: u8, offsets: &[(i8, i8)], moves: &mut Vec<Move>) {
+        for &step in offsets {
+            if let Some(end) = offset(square, step) {
+                if self.squares[end as usize].is_none() || self.is_enemy_reference(end) {
+                    moves.push(Move::new(square, end, MoveFlag::None));
+                }
+            }
+        }
+    }
+
+    fn add_slides_reference(&self, square: u8, offsets: &[(i8, i8)], moves: &mut Vec<Move>) {
+        for &direction in offsets {
+            let mut current = offset(square, direction);
+
+            while let Some(end) = current {
+                if self.squares[end as usize].is_some() && !self.is_enemy_reference(end) {
+                    break;
+                }
+
+                moves.push(Move::new(square, end, MoveFlag::None));
+
+                if self.squares[end as usize].is_some() {
+                    break;
+                }
+
+                current = offset(end, direction);
+            }
+        }
+    }
+
+    // Everything between the outermost of the four squares involved has to be empty, apart from the
+    // castling pieces, and no square the king stands on or crosses may be attacked
+    fn add_castles_reference(&self, king_square: u8, moves: &mut Vec<Move>) {
+        let color = self.turn;
+
+        for castle in [color | PieceType::King, color | PieceType::Queen] {
+            let Some(rook_square) = self.get_castling_rook(castle) else {
+                continue;
+            };
+
+            let r#move = Move::new(king_square, rook_square, MoveFlag::Castle);
+            let (king_end, rook_end) = r#move.get_castling_squares();
+
+            let mut squares = self.squares;
+            squares[king_square as usize] = None;
+            squares[rook_square as usize] = None;
+
+            let involved = [king_square, king_end, rook_square, rook_end];
+            let first = *involved.iter().min().unwrap();
+            let last = *involved.iter().max().unwrap();
+
+            if (first..=last).any(|square| squares[square as usize].is_some()) {
+                continue;
+            }
+
+            let (first, last) = (king_square.min(king_end), king_square.max(king_end));
+
+            if (first..=last).any(|square| is_attacked(&squares, square, color.opposite())) {
+                continue;
+            }
+
+            moves.push(r#move);
+        }
+    }
+}
+
+// Answers bisections without needing Stockfish, at the cost of being many times slower
+pub struct ReferenceGenerator;
+
+impl PerftOracle for ReferenceGenerator {
+    fn divide(&mut self, board: &Board, depth: u8) -> Vec<(String, u64)> {
+        let mut board = board.clone();
+
+        board
+            .calculate_moves_reference()
+            .into_iter()
+            .map(|r#move| {
+                board.make_move(r#move);
+                let count = board.perft_reference(depth - 1);
+                board.undo_move(r#move);
+
+                (r#move.to_uci(board.chess960), count)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn sorted(mut moves: Vec<Move>) -> Vec<Move> {
+        moves.sort_by_key(|r#move| {
+            (
+                r#move.get_start(),
+                r#move.get_end(),
+                u8::from(r#move.get_flag()),
+            )
+        });
+        moves
+    }
+
+    #[test]
+    fn perft_positions() {
+        for position in PERFT_POSITIONS {
+            let mut board = Board::from_fen(position.fen);
+
+            for (depth, expected) in position.get_depths(10_000) {
+                assert_eq!(board.perft_reference(depth), expected, "{}", position.name);
+            }
+        }
+    }
+
+    #[test]
+    fn oracle() {
+        let board = Board::from_fen(PERFT_POSITIONS[1].fen);
+
+        assert_eq!(board.bisect(&mut ReferenceGenerator, 2), None);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_calculate_moves(mut board in perft_board(), choices in choices(80)) {
+            playout(&mut board, &choices, |board, moves, _| {
+                prop_assert_eq!(
+                    sorted(moves.to_vec()),
+                    sorted(board.calculate_moves_reference()),
+                    "{}",
+                    board.to_fen()
+                );
+                Ok(())
+            })?;
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use super::*;
+use std::ops::*;
+
+impl Neg for Score {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Score(-self.0)
+    }
+}
+
+impl Add<Score> for Score {
+    type Output = Self;
+    fn add(self, rhs: Score) -> Self::Output {
+        Score(self.0 + rhs.0)
+    }
+}
+
+impl Add<i32> for Score {
+    type Output = Self;
+    fn add(self, rhs: i32) -> Self::Output {
+        Score(self.0 + rhs)
+    }
+}
+
+impl Sub<Score> for Score {
+    type Output = Self;
+    fn sub(self, rhs: Score) -> Self::Output {
+        Score(self.0 - rhs.0)
+    }
+}
+
+impl Sub<i32> for Score {
+    type Output = Self;
+    fn sub(self, rhs: i32) -> Self::Output {
+        Score(self.0 - rhs)
+    }
+}
+
+impl AddAssign<Score> for Score {
+    fn add_assign(&mut self, rhs: Score) {
+        self.0 += rhs.0
+    }
+}
+
+impl AddAssign<i32> for Score {
+    fn add_assign(&mut self, rhs: i32) {
+        self.0 += rhs
+    }
+}
+
+impl SubAssign<Score> for Score {
+    fn sub_assign(&mut self, rhs: Score) {
+        self.0 -= rhs.0
+    }
+}
+
+impl SubAssign<i32> for Score {
+    fn sub_assign(&mut self, rhs: i32) {
+        self.0 -= rhs
+    }
+}
@@ -0,0 +1,80 @@
+mod _debug;
+mod _ops;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Score(i32);
+
+impl Score {
+    pub const ZERO: Score = Score(0);
+    pub const DRAW: Score = Score(0);
+    pub const MATE: Score = Score(32000);
+    pub const INFINITY: Score = Score(32001);
+
+    // Any score beyond this is a forced mate found within the maximum search ply
+    pub const MATE_BOUND: Score = Score(32000 - 256);
+
+    pub fn mate_in(ply: usize) -> Self {
+        Score(Self::MATE.0 - ply as i32)
+    }
+
+    pub fn mated_in(ply: usize) -> Self {
+        Score(-Self::MATE.0 + ply as i32)
+    }
+
+    pub fn is_mate(&self) -> bool {
+        self.0.abs() > Self::MATE_BOUND.0
+    }
+
+    // Number of full moves until mate, negative when we are the side getting mated
+    pub fn get_mate_moves(&self) -> Option<i32> {
+        if !self.is_mate() {
+            return None;
+        }
+
+        if self.0 > 0 {
+            Some((Self::MATE.0 - self.0 + 1) / 2)
+        } else {
+            Some(-(Self::MATE.0 + self.0) / 2)
+        }
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<i32> for Score {
+    fn from(i32: i32) -> Self {
+        Score(i32)
+    }
+}
+
+impl From<Score> for i32 {
+    fn from(score: Score) -> Self {
+        score.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mate_moves() {
+        assert_eq!(Score::mate_in(1).get_mate_moves(), Some(1));
+        assert_eq!(Score::mate_in(3).get_mate_moves(), Some(2));
+        assert_eq!(Score::mated_in(0).get_mate_moves(), Some(0));
+        assert_eq!(Score::mated_in(2).get_mate_moves(), Some(-1));
+        assert_eq!(Score::from(150).get_mate_moves(), None);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(Score::mate_in(1) > Score::mate_in(3));
+        assert!(Score::mated_in(1) < Score::mated_in(3));
+        assert!(Score::mated_in(10) < Score::from(-5000));
+        assert!(-Score::INFINITY < Score::mated_in(0));
+    }
+}
@@ -0,0 +1,220 @@
+use super::*;
+
+impl<E: Evaluator> Search<E> {
+    // https://www.chessprogramming.org/Alpha-Beta#Negamax_Framework
+    pub(super) fn negamax(
+        &mut self,
+        depth: u8,
+        ply: usize,
+        mut alpha: Score,
+        beta: Score,
+    ) -> Score {
+        self.pv[ply].clear();
+
+        // A position reached again can be repeated until the third time by the side it suits, so
+        // the first repetition already scores as a draw, as does reaching the fifty-move rule. The
+        // root still has to find a move
+        // https://www.chessprogramming.org/Repetitions
+        if ply > 0 && (self.board.get_repetitions() > 1 || self.board.get_state().halfmove >= 100) {
+            return Score::DRAW;
+        }
+
+        if depth == 0 || ply >= MAX_PLY {
+            return self.quiescence(ply, alpha, beta);
+        }
+
+        self.visit_node();
+        if self.stopped {
+            return Score::ZERO;
+        }
+
+        let key = self.board.get_key();
+        let entry = self.table.probe(key);
+
+        // The root always searches so that it produces a best move and a principal variation
+        if let Some(entry) = entry {
+            if ply > 0 && entry.depth >= depth {
+                let score = Self::score_from_table(entry.score, ply);
+
+                match entry.bound {
+                    Bound::Exact => return score,
+                    Bound::Lower if score >= beta => return score,
+                    Bound::Upper if score <= alpha => return score,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut moves = self.board.calculate_moves();
+
+        if moves.is_empty() {
+            return if self.is_in_check() {
+                Score::mated_in(ply)
+            } else {
+                Score::DRAW
+            };
+        }
+
+        self.order_moves(&mut moves, ply, entry.and_then(|entry| entry.best_move));
+
+        let original_alpha = alpha;
+        let mut best_score = -Score::INFINITY;
+        let mut best_move = None;
+
+        for r#move in moves {
+            self.board.make_move(r#move);
+            let score = -self.negamax(depth - 1, ply + 1, -beta, -alpha);
+            self.board.undo_move(r#move);
+
+            if self.stopped {
+                return Score::ZERO;
+            }
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(r#move);
+            }
+
+            if score > alpha {
+                alpha = score;
+                self.update_pv(ply, r#move);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best_score >= beta {
+            Bound::Lower
+        } else if best_score > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+
+        self.table.store(
+            key,
+            best_move,
+            Self::score_to_table(best_score, ply),
+            depth,
+            bound,
+        );
+
+        best_score
+    }
+
+    // https://www.chessprogramming.org/Quiescence_Search
+    fn quiescence(&mut self, ply: usize, mut alpha: Score, beta: Score) -> Score {
+        self.pv[ply].clear();
+
+        self.visit_node();
+        if self.stopped {
+            return Score::ZERO;
+        }
+
+        let in_check = self.is_in_check();
+
+        // Every move has to be considered while in check, otherwise the side to move may stand pat
+        // and only captures are searched
+        let gen_type = if in_check {
+            GenType::Evasions
+        } else {
+            GenType::Captures
+        };
+        let mut moves = MoveList::new();
+        self.board.generate_moves(gen_type, &mut moves);
+
+        // Without captures the side to move stands pat, even when it is stalemated. Telling the two
+        // apart would mean generating the quiet moves at every leaf, and the main search already
+        // scores stalemates within its depth
+        if in_check && moves.is_empty() {
+            return Score::mated_in(ply);
+        }
+
+        if ply >= MAX_PLY {
+            return self.evaluator.evaluate(&self.board);
+        }
+
+        let mut best_score = -Score::INFINITY;
+
+        if !in_check {
+            best_score = self.evaluator.evaluate(&self.board);
+
+            if best_score >= beta {
+                return best_score;
+            }
+
+            if best_score > alpha {
+                alpha = best_score;
+            }
+        }
+
+        self.order_moves(&mut moves, ply, None);
+
+        for r#move in moves {
+            self.board.make_move(r#move);
+            let score = -self.quiescence(ply + 1, -beta, -alpha);
+            self.board.undo_move(r#move);
+
+            if self.stopped {
+                return Score::ZERO;
+            }
+
+            if score > best_score {
+                best_score = score;
+            }
+
+            if score > alpha {
+                alpha = score;
+                self.update_pv(ply, r#move);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    fn visit_node(&mut self) {
+        self.nodes += 1;
+
+        if self.nodes.is_multiple_of(2048) {
+            self.check_limits();
+        }
+    }
+
+    // Mate scores are stored relative to the node rather than the root, since the same position can
+    // be reached at a different ply
+    // https://www.chessprogramming.org/Transposition_Table#Mate_Scores
+    fn score_to_table(score: Score, ply: usize) -> Score {
+        match score.get_mate_moves() {
+            Some(moves) if moves > 0 => score + ply as i32,
+            Some(_) => score - ply as i32,
+            None => score,
+        }
+    }
+
+    fn score_from_table(score: Score, ply: usize) -> Score {
+        match score.get_mate_moves() {
+            Some(moves) if moves > 0 => score - ply as i32,
+            Some(_) => score + ply as i32,
+            None => score,
+        }
+    }
+
+    fn is_in_check(&self) -> bool {
+        self.board.is_in_check()
+    }
+
+    fn update_pv(&mut self, ply: usize, r#move: Move) {
+        let (current, next) = self.pv.split_at_mut(ply + 1);
+        let pv = &mut current[ply];
+
+        pv.clear();
+        pv.push(r#move);
+        pv.extend_from_slice(&next[0]);
+    }
+}
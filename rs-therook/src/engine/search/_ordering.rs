@@ -0,0 +1,55 @@
+use super::*;
+
+impl<E: Evaluator> Search<E> {
+    // Transposition table move first, then the previous principal variation, then captures by
+    // MVV-LVA, then promotions, then quiet moves
+    pub(super) fn order_moves(&self, moves: &mut [Move], ply: usize, hash_move: Option<Move>) {
+        let pv_move = self.previous_pv.get(ply).copied();
+
+        moves.sort_by_cached_key(|r#move| {
+            if Some(*r#move) == hash_move {
+                return i32::MIN;
+            }
+
+            if Some(*r#move) == pv_move {
+                return i32::MIN + 1;
+            }
+
+            let mut key = 0;
+
+            if let Some(captured) = self.get_captured(*r#move) {
+                let attacker = self.board.squares[r#move.get_start() as usize].unwrap();
+                key -=
+                    10_000 + Self::get_value(captured) * 10 - Self::get_value(attacker.get_type());
+            }
+
+            if let Some(r#type) = r#move.get_promote_piece_type() {
+                key -= 1_000 + Self::get_value(r#type);
+            }
+
+            key
+        });
+    }
+
+    fn get_captured(&self, r#move: Move) -> Option<PieceType> {
+        match r#move.get_flag() {
+            MoveFlag::EnPassant => return Some(PieceType::Pawn),
+            // Castling is written as the king taking its own rook
+            MoveFlag::Castle => return None,
+            _ => {}
+        }
+
+        self.board.squares[r#move.get_end() as usize].map(|piece| piece.get_type())
+    }
+
+    pub(super) fn get_value(r#type: PieceType) -> i32 {
+        match r#type {
+            PieceType::King => 0,
+            PieceType::Queen => 900,
+            PieceType::Rook => 500,
+            PieceType::Bishop => 330,
+            PieceType::Knight => 320,
+            PieceType::Pawn => 100,
+        }
+    }
+}
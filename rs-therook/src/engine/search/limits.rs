@@ -0,0 +1,8 @@
+use std::time::Duration;
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct SearchLimits {
+    pub depth: Option<u8>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
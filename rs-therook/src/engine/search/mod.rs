@@ -0,0 +1,323 @@
+mod _negamax;
+mod _ordering;
+mod limits;
+mod result;
+
+use super::*;
+pub use limits::*;
+pub use result::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+pub const MAX_PLY: usize = 128;
+
+pub struct Search<E: Evaluator = HandCrafted> {
+    board: Board,
+    evaluator: E,
+    limits: SearchLimits,
+    table: Arc<TranspositionTable>,
+    stop: Arc<AtomicBool>,
+
+    start: Instant,
+    nodes: u64,
+    stopped: bool,
+
+    // Triangular principal variation table, pv[ply] holds the best line found from that ply
+    pv: Vec<Vec<Move>>,
+    previous_pv: Vec<Move>,
+}
+
+impl Search {
+    pub fn new(
+        board: Board,
+        limits: SearchLimits,
+        table: Arc<TranspositionTable>,
+        stop: Arc<AtomicBool>,
+    ) -> Self {
+        Search::with_evaluator(board, HandCrafted, limits, table, stop)
+    }
+}
+
+impl<E: Evaluator> Search<E> {
+    pub fn with_evaluator(
+        board: Board,
+        evaluator: E,
+        limits: SearchLimits,
+        table: Arc<TranspositionTable>,
+        stop: Arc<AtomicBool>,
+    ) -> Self {
+        Search {
+            board,
+            evaluator,
+            limits,
+            table,
+            stop,
+
+            start: Instant::now(),
+            nodes: 0,
+            stopped: false,
+
+            pv: vec![vec![]; MAX_PLY + 1],
+            previous_pv: vec![],
+        }
+    }
+
+    // https://www.chessprogramming.org/Iterative_Deepening
+    pub fn run(&mut self, mut report: impl FnMut(&SearchResult)) -> SearchResult {
+        self.start = Instant::now();
+        self.nodes = 0;
+        self.stopped = false;
+        self.table.new_search();
+
+        let max_depth = self
+            .limits
+            .depth
+            .unwrap_or(MAX_PLY as u8)
+            .min(MAX_PLY as u8);
+
+        // Fall back to any legal move in case the first iteration gets interrupted
+        let mut result = SearchResult {
+            best_move: self.board.calculate_moves().first().copied(),
+            score: Score::ZERO,
+            depth: 0,
+            nodes: 0,
+            time: self.start.elapsed(),
+            pv: vec![],
+        };
+
+        for depth in 1..=max_depth {
+            self.check_limits();
+            if self.stopped {
+                break;
+            }
+
+            let score = self.negamax(depth, 0, -Score::INFINITY, Score::INFINITY);
+
+            // Results of an unfinished iteration cannot be trusted
+            if self.stopped {
+                break;
+            }
+
+            self.previous_pv = self.pv[0].clone();
+
+            result = SearchResult {
+                best_move: self.pv[0].first().copied().or(result.best_move),
+                score,
+                depth,
+                nodes: self.nodes,
+                time: self.start.elapsed(),
+                pv: self.pv[0].clone(),
+            };
+
+            report(&result);
+
+            // No point searching deeper once a forced mate fits within the current depth
+            if let Some(moves) = score.get_mate_moves() {
+                if (moves.unsigned_abs() * 2) as u8 <= depth {
+                    break;
+                }
+            }
+        }
+
+        result.nodes = self.nodes;
+        result.time = self.start.elapsed();
+        result
+    }
+
+    fn check_limits(&mut self) {
+        if self.stop.load(Ordering::Relaxed) {
+            self.stopped = true;
+        }
+
+        if let Some(nodes) = self.limits.nodes {
+            if self.nodes >= nodes {
+                self.stopped = true;
+            }
+        }
+
+        if let Some(time) = self.limits.time {
+            if self.start.elapsed() >= time {
+                self.stopped = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(board: Board, limits: SearchLimits) -> SearchResult {
+        Search::new(
+            board,
+            limits,
+            Arc::new(TranspositionTable::new(1)),
+            Arc::new(AtomicBool::new(false)),
+        )
+        .run(|_| {})
+    }
+
+    fn depth(depth: u8) -> SearchLimits {
+        SearchLimits {
+            depth: Some(depth),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn mate_in_one() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        let result = search(board, depth(3));
+
+        assert_eq!(
+            result.best_move,
+            Some(Move::new(square!(A1), square!(A8), MoveFlag::None))
+        );
+        assert_eq!(result.score.get_mate_moves(), Some(1));
+    }
+
+    #[test]
+    fn mate_in_two() {
+        let board = Board::from_fen("k7/8/2K5/8/8/8/8/1R6 w - - 0 1");
+        let result = search(board, depth(4));
+
+        assert_eq!(result.score.get_mate_moves(), Some(2));
+    }
+
+    #[test]
+    fn mated() {
+        let board = Board::from_fen("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1");
+        let result = search(board, depth(3));
+
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, Score::mated_in(0));
+    }
+
+    #[test]
+    fn stalemate() {
+        let board = Board::from_fen("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1");
+        let result = search(board, depth(3));
+
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, Score::DRAW);
+    }
+
+    #[test]
+    fn repetition_is_a_draw() {
+        let mut board = Board::from_fen("4k2q/8/8/8/8/8/8/4K1N1 w - - 0 1");
+        for r#move in [
+            Move::new(square!(G1), square!(F3), MoveFlag::None),
+            Move::new(square!(H8), square!(H7), MoveFlag::None),
+            Move::new(square!(F3), square!(G1), MoveFlag::None),
+            Move::new(square!(H7), square!(H8), MoveFlag::None),
+        ] {
+            board.make_move(r#move);
+        }
+
+        // Down a queen, White's best is to repeat the position with Nf3
+        let result = search(board, depth(3));
+
+        assert_eq!(
+            result.best_move,
+            Some(Move::new(square!(G1), square!(F3), MoveFlag::None))
+        );
+        assert_eq!(result.score, Score::DRAW);
+    }
+
+    #[test]
+    fn fifty_move_rule() {
+        let board = Board::from_fen("6k1/8/8/8/8/8/8/Q6K w - - 99 60");
+        let result = search(board, depth(3));
+
+        assert_eq!(result.score, Score::DRAW);
+    }
+
+    #[test]
+    fn captures_hanging_queen() {
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1");
+        let result = search(board, depth(2));
+
+        assert_eq!(
+            result.best_move,
+            Some(Move::new(square!(D2), square!(D5), MoveFlag::None))
+        );
+    }
+
+    #[test]
+    fn castling_is_not_a_capture() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/p7/R3K2R w KQ - 0 1");
+        let mut moves = board.calculate_moves().to_vec();
+
+        let search = Search::new(
+            board,
+            depth(1),
+            Arc::new(TranspositionTable::new(1)),
+            Arc::new(AtomicBool::new(false)),
+        );
+        search.order_moves(&mut moves, 0, None);
+
+        assert_eq!(
+            moves[0],
+            Move::new(square!(A1), square!(A2), MoveFlag::None)
+        );
+    }
+
+    #[test]
+    fn pv_is_playable() {
+        let mut board = Board::initial();
+        let result = search(board.clone(), depth(4));
+
+        assert_eq!(result.depth, 4);
+        assert_eq!(result.pv.first().copied(), result.best_move);
+
+        for r#move in result.pv {
+            assert!(board.calculate_moves().contains(&r#move));
+            board.make_move(r#move);
+        }
+    }
+
+    #[test]
+    fn shares_table() {
+        let board = Board::initial();
+        let table = Arc::new(TranspositionTable::new(1));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let first = Search::new(board.clone(), depth(4), table.clone(), stop.clone()).run(|_| {});
+        assert!(table.hashfull() > 0);
+
+        // The second search starts from the stored entries and needs fewer nodes for the same depth
+        let second = Search::new(board, depth(4), table, stop).run(|_| {});
+        assert!(second.nodes < first.nodes);
+    }
+
+    #[test]
+    fn node_limit() {
+        let board = Board::initial();
+        let result = search(
+            board,
+            SearchLimits {
+                nodes: Some(5_000),
+                ..Default::default()
+            },
+        );
+
+        assert!(result.best_move.is_some());
+        assert!(result.nodes < 5_000 + 2048);
+    }
+
+    #[test]
+    fn stop_flag() {
+        let board = Board::initial();
+        let result = Search::new(
+            board,
+            depth(64),
+            Arc::new(TranspositionTable::new(1)),
+            Arc::new(AtomicBool::new(true)),
+        )
+        .run(|_| {});
+
+        assert_eq!(result.depth, 0);
+        assert!(result.best_move.is_some());
+    }
+}
@@ -0,0 +1,18 @@
+use super::*;
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub best_move: Option<Move>,
+    pub score: Score,
+    pub depth: u8,
+    pub nodes: u64,
+    pub time: Duration,
+    pub pv: Vec<Move>,
+}
+
+impl SearchResult {
+    pub fn get_nps(&self) -> u64 {
+        self.nodes * 1000 / (self.time.as_millis() as u64).max(1)
+    }
+}
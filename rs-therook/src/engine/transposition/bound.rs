@@ -0,0 +1,31 @@
+use Bound::*;
+
+const EXACT: u8 = 0;
+const LOWER: u8 = 1;
+const UPPER: u8 = 2;
+
+// https://www.chessprogramming.org/Node_Types
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Exact = EXACT,
+    Lower = LOWER,
+    Upper = UPPER,
+}
+
+impl From<Bound> for u8 {
+    fn from(bound: Bound) -> Self {
+        bound as u8
+    }
+}
+
+impl From<u8> for Bound {
+    fn from(u8: u8) -> Self {
+        match u8 {
+            EXACT => Exact,
+            LOWER => Lower,
+            UPPER => Upper,
+            _ => panic!("Unknown bound: {u8:?}"),
+        }
+    }
+}
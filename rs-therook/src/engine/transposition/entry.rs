@@ -0,0 +1,68 @@
+use super::*;
+
+// Packed into the 64 bit data word of a slot as
+// | 16 bits move | 16 bits score | 16 bits unused | 8 bits depth | 2 bits bound | 6 bits generation |
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TranspositionEntry {
+    pub best_move: Option<Move>,
+    pub score: Score,
+    pub depth: u8,
+    pub bound: Bound,
+    pub generation: u8,
+}
+
+impl TranspositionEntry {
+    pub(super) const GENERATION_MASK: u64 = 0b111111;
+    pub(super) const BOUND_SHIFT: u64 = 6;
+    pub(super) const DEPTH_SHIFT: u64 = 8;
+    pub(super) const PAYLOAD_SHIFT: u64 = 16;
+
+    pub(super) fn get_depth(data: u64) -> u8 {
+        (data >> Self::DEPTH_SHIFT) as u8
+    }
+
+    pub(super) fn get_generation(data: u64) -> u8 {
+        (data & Self::GENERATION_MASK) as u8
+    }
+
+    pub(super) fn pack(depth: u8, bound: Bound, generation: u8, payload: u64) -> u64 {
+        payload << Self::PAYLOAD_SHIFT
+            | (depth as u64) << Self::DEPTH_SHIFT
+            | (u8::from(bound) as u64) << Self::BOUND_SHIFT
+            | generation as u64 & Self::GENERATION_MASK
+    }
+}
+
+impl From<TranspositionEntry> for u64 {
+    fn from(entry: TranspositionEntry) -> Self {
+        let r#move = entry.best_move.map(u16::from).unwrap_or(0) as u64;
+        let score = i32::from(entry.score) as i16 as u16 as u64;
+
+        TranspositionEntry::pack(
+            entry.depth,
+            entry.bound,
+            entry.generation,
+            r#move << 32 | score << 16,
+        )
+    }
+}
+
+impl From<u64> for TranspositionEntry {
+    fn from(data: u64) -> Self {
+        let payload = data >> TranspositionEntry::PAYLOAD_SHIFT;
+        let r#move = (payload >> 32) as u16;
+        let score = (payload >> 16) as u16 as i16 as i32;
+
+        TranspositionEntry {
+            best_move: if r#move == 0 {
+                None
+            } else {
+                Some(Move::from(r#move))
+            },
+            score: Score::from(score),
+            depth: Self::get_depth(data),
+            bound: Bound::from((data >> Self::BOUND_SHIFT) as u8 & 0b11),
+            generation: Self::get_generation(data),
+        }
+    }
+}
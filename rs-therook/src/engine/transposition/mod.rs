@@ -0,0 +1,355 @@
+mod bound;
+mod entry;
+
+use super::*;
+pub use bound::*;
+pub use entry::*;
+use std::collections::TryReserveError;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+
+// https://www.chessprogramming.org/Transposition_Table
+//
+// Every slot stores its key XOR'ed with its data, so a slot torn by two threads writing at the same
+// time no longer matches either key and reads back as a miss instead of corrupted data
+// https://www.chessprogramming.org/Shared_Hash_Table#Lock-less
+#[derive(Default)]
+struct Slot {
+    check: AtomicU64,
+    data: AtomicU64,
+}
+
+impl Slot {
+    fn load(&self) -> (u64, u64) {
+        let data = self.data.load(Ordering::Relaxed);
+        let check = self.check.load(Ordering::Relaxed);
+        (check ^ data, data)
+    }
+
+    fn save(&self, key: u64, data: u64) {
+        self.check.store(key ^ data, Ordering::Relaxed);
+        self.data.store(data, Ordering::Relaxed);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.load(Ordering::Relaxed) == 0
+    }
+}
+
+// Four slots of 16 bytes share a single cache line
+#[derive(Default)]
+#[repr(align(64))]
+struct Bucket([Slot; TranspositionTable::BUCKET_SIZE]);
+
+pub struct TranspositionTable {
+    buckets: Vec<Bucket>,
+    generation: AtomicU8,
+}
+
+impl TranspositionTable {
+    pub const DEFAULT_MB: usize = 16;
+    const BUCKET_SIZE: usize = 4;
+
+    // Counts from perft are stored in the 48 bit payload of the data word
+    const PERFT_LIMIT: u64 = 1 << 48;
+
+    pub fn new(mb: usize) -> Self {
+        Self::try_new(mb).expect("Not enough memory for the transposition table")
+    }
+
+    // Every bucket is filled in straight away, so a size the machine can't hold is reported instead
+    // of aborting the process
+    pub fn try_new(mb: usize) -> Result<Self, TryReserveError> {
+        let count = (mb.saturating_mul(1024 * 1024) / size_of::<Bucket>()).max(1);
+
+        let mut buckets = Vec::new();
+        buckets.try_reserve_exact(count)?;
+        buckets.resize_with(count, Bucket::default);
+
+        Ok(TranspositionTable {
+            buckets,
+            generation: AtomicU8::new(0),
+        })
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.buckets.len() * size_of::<Bucket>() / 1024 / 1024
+    }
+
+    pub fn clear(&self) {
+        for bucket in &self.buckets {
+            for slot in &bucket.0 {
+                slot.save(0, 0);
+            }
+        }
+
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    // Entries from older searches become the first candidates for replacement
+    pub fn new_search(&self) {
+        let generation = self.get_generation();
+        self.generation.store(
+            (generation + 1) & TranspositionEntry::GENERATION_MASK as u8,
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn get_generation(&self) -> u8 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    pub fn probe(&self, key: u64) -> Option<TranspositionEntry> {
+        self.probe_data(key).map(TranspositionEntry::from)
+    }
+
+    pub fn store(&self, key: u64, best_move: Option<Move>, score: Score, depth: u8, bound: Bound) {
+        // Keep the move from a previous visit rather than forgetting it when this one found none
+        let best_move = best_move.or_else(|| self.probe(key).and_then(|entry| entry.best_move));
+
+        self.store_data(
+            key,
+            TranspositionEntry {
+                best_move,
+                score,
+                depth,
+                bound,
+                generation: self.get_generation(),
+            }
+            .into(),
+        );
+    }
+
+    pub fn probe_perft(&self, key: u64, depth: u8) -> Option<u64> {
+        let data = self.probe_data(key)?;
+
+        if TranspositionEntry::get_depth(data) != depth {
+            return None;
+        }
+
+        Some(data >> TranspositionEntry::PAYLOAD_SHIFT)
+    }
+
+    pub fn store_perft(&self, key: u64, depth: u8, count: u64) {
+        if count >= Self::PERFT_LIMIT {
+            return;
+        }
+
+        self.store_data(
+            key,
+            TranspositionEntry::pack(depth, Bound::Exact, self.get_generation(), count),
+        );
+    }
+
+    // Permille of the table used by the current search, estimated from the first thousand slots
+    pub fn hashfull(&self) -> u16 {
+        let generation = self.get_generation();
+        let (slots, used) = self
+            .buckets
+            .iter()
+            .flat_map(|bucket| &bucket.0)
+            .take(1000)
+            .fold((0, 0), |(slots, used), slot| {
+                let current = !slot.is_empty()
+                    && TranspositionEntry::get_generation(slot.load().1) == generation;
+
+                (slots + 1, used + usize::from(current))
+            });
+
+        (used * 1000 / slots) as u16
+    }
+
+    fn get_bucket(&self, key: u64) -> &Bucket {
+        // Maps the key onto the table without needing a power of two size
+        let index = (key as u128 * self.buckets.len() as u128) >> 64;
+        &self.buckets[index as usize]
+    }
+
+    fn probe_data(&self, key: u64) -> Option<u64> {
+        self.get_bucket(key)
+            .0
+            .iter()
+            .map(Slot::load)
+            .find(|&(slot_key, data)| data != 0 && slot_key == key)
+            .map(|(_, data)| data)
+    }
+
+    // Overwrite the slot holding the same position if there is one, otherwise replace the slot
+    // that is the cheapest to lose, where shallow entries from old searches go first
+    // https://www.chessprogramming.org/Transposition_Table#Replacement_Strategies
+    fn store_data(&self, key: u64, data: u64) {
+        let generation = self.get_generation();
+        let bucket = self.get_bucket(key);
+
+        let slot = bucket
+            .0
+            .iter()
+            .find(|slot| !slot.is_empty() && slot.load().0 == key)
+            .or_else(|| bucket.0.iter().find(|slot| slot.is_empty()))
+            .unwrap_or_else(|| {
+                bucket
+                    .0
+                    .iter()
+                    .min_by_key(|slot| {
+                        let data = slot.load().1;
+                        let age = generation.wrapping_sub(TranspositionEntry::get_generation(data))
+                            & TranspositionEntry::GENERATION_MASK as u8;
+
+                        TranspositionEntry::get_depth(data) as i32 - 8 * age as i32
+                    })
+                    .unwrap()
+            });
+
+        slot.save(key, data);
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        TranspositionTable::new(Self::DEFAULT_MB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(table: &TranspositionTable, depth: u8) -> TranspositionEntry {
+        TranspositionEntry {
+            best_move: Some(Move::new(square!(E2), square!(E4), MoveFlag::PawnDash)),
+            score: Score::from(-150),
+            depth,
+            bound: Bound::Lower,
+            generation: table.get_generation(),
+        }
+    }
+
+    #[test]
+    fn entry_round_trip() {
+        let table = TranspositionTable::new(1);
+        let entry = entry(&table, 12);
+
+        assert_eq!(TranspositionEntry::from(u64::from(entry)), entry);
+
+        let mate = TranspositionEntry {
+            best_move: None,
+            score: Score::mated_in(3),
+            bound: Bound::Upper,
+            ..entry
+        };
+        assert_eq!(TranspositionEntry::from(u64::from(mate)), mate);
+    }
+
+    #[test]
+    fn store_and_probe() {
+        let table = TranspositionTable::new(1);
+        let expected = entry(&table, 5);
+
+        assert_eq!(table.probe(0x1234), None);
+
+        table.store(
+            0x1234,
+            expected.best_move,
+            expected.score,
+            expected.depth,
+            expected.bound,
+        );
+        assert_eq!(table.probe(0x1234), Some(expected));
+        assert_eq!(table.probe(0x4321), None);
+    }
+
+    #[test]
+    fn keeps_previous_move() {
+        let table = TranspositionTable::new(1);
+        let expected = entry(&table, 5);
+
+        table.store(0x1234, expected.best_move, expected.score, 5, Bound::Lower);
+        table.store(0x1234, None, Score::ZERO, 6, Bound::Upper);
+
+        let entry = table.probe(0x1234).unwrap();
+        assert_eq!(entry.best_move, expected.best_move);
+        assert_eq!(entry.depth, 6);
+        assert_eq!(entry.bound, Bound::Upper);
+    }
+
+    #[test]
+    fn replaces_shallowest() {
+        let table = TranspositionTable::new(0);
+        assert_eq!(table.buckets.len(), 1);
+
+        for key in 1..=4 {
+            table.store(key, None, Score::ZERO, key as u8 * 2, Bound::Exact);
+        }
+
+        table.store(5, None, Score::ZERO, 3, Bound::Exact);
+        assert_eq!(table.probe(1), None);
+        assert!((2..=5).all(|key| table.probe(key).is_some()));
+    }
+
+    #[test]
+    fn too_large() {
+        assert!(TranspositionTable::try_new(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn replaces_old_generation() {
+        let table = TranspositionTable::new(0);
+
+        for key in 1..=4 {
+            table.store(key, None, Score::ZERO, 20 - key as u8, Bound::Exact);
+            table.new_search();
+        }
+
+        // Deepest entry, but stored four searches ago
+        table.store(5, None, Score::ZERO, 1, Bound::Exact);
+        assert_eq!(table.probe(1), None);
+        assert!((2..=5).all(|key| table.probe(key).is_some()));
+    }
+
+    #[test]
+    fn torn_slot_is_a_miss() {
+        let table = TranspositionTable::new(1);
+        table.store(0x1234, None, Score::ZERO, 5, Bound::Exact);
+
+        let slot = table
+            .get_bucket(0x1234)
+            .0
+            .iter()
+            .find(|slot| !slot.is_empty())
+            .unwrap();
+        slot.data.fetch_xor(1 << 20, Ordering::Relaxed);
+
+        assert_eq!(table.probe(0x1234), None);
+    }
+
+    #[test]
+    fn perft() {
+        let table = TranspositionTable::new(1);
+        table.store_perft(0x1234, 4, 197281);
+
+        assert_eq!(table.probe_perft(0x1234, 4), Some(197281));
+        assert_eq!(table.probe_perft(0x1234, 3), None);
+    }
+
+    #[test]
+    fn hashfull_and_clear() {
+        let table = TranspositionTable::new(1);
+        assert_eq!(table.hashfull(), 0);
+
+        for key in 0..100_000u64 {
+            table.store(
+                key.wrapping_mul(0x9E3779B97F4A7C15),
+                None,
+                Score::ZERO,
+                1,
+                Bound::Exact,
+            );
+        }
+        assert!(table.hashfull() > 500);
+
+        table.new_search();
+        assert_eq!(table.hashfull(), 0);
+
+        table.clear();
+        assert_eq!(table.probe(0x9E3779B97F4A7C15), None);
+    }
+}
@@ -0,0 +1,40 @@
+mod _random;
+
+use super::*;
+use _random::*;
+
+// Keys are taken from the Polyglot random table so that position keys can be used to probe opening books
+pub struct Zobrist;
+
+impl Zobrist {
+    pub fn piece(piece: Piece, square: u8) -> u64 {
+        // Polyglot interleaves colors, starting from the black pawn
+        let kind = match piece.get_type() {
+            PieceType::Pawn => 0,
+            PieceType::Knight => 1,
+            PieceType::Bishop => 2,
+            PieceType::Rook => 3,
+            PieceType::Queen => 4,
+            PieceType::King => 5,
+        } * 2
+            + match piece.get_color() {
+                PieceColor::Black => 0,
+                PieceColor::White => 1,
+            };
+
+        POLYGLOT_RANDOM[64 * kind + square as usize]
+    }
+
+    // Indexed the same way as BoardState::castling
+    pub fn castling(index: usize) -> u64 {
+        POLYGLOT_RANDOM[768 + index]
+    }
+
+    pub fn enpassant(file: u8) -> u64 {
+        POLYGLOT_RANDOM[772 + file as usize]
+    }
+
+    pub fn turn() -> u64 {
+        POLYGLOT_RANDOM[780]
+    }
+}
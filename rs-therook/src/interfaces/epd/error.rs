@@ -0,0 +1,32 @@
+use super::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EpdError {
+    // Fewer than the four position fields
+    MissingFields,
+    InvalidFen(FenError),
+    InvalidOpcode(String),
+    UnterminatedString,
+    InvalidOperand(String, String),
+    InvalidMove(String, SanError),
+    // Line number within a suite, counting from 1
+    AtLine(usize, Box<EpdError>),
+}
+
+impl std::fmt::Display for EpdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EpdError::MissingFields => write!(f, "EPD needs at least four position fields"),
+            EpdError::InvalidFen(error) => write!(f, "Invalid position: {error}"),
+            EpdError::InvalidOpcode(opcode) => write!(f, "Invalid opcode: {opcode}"),
+            EpdError::UnterminatedString => write!(f, "String operand is missing its closing \""),
+            EpdError::InvalidOperand(opcode, operand) => {
+                write!(f, "Invalid operand for {opcode}: {operand}")
+            }
+            EpdError::InvalidMove(opcode, error) => write!(f, "{error} in {opcode}"),
+            EpdError::AtLine(line, error) => write!(f, "{error} on line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for EpdError {}
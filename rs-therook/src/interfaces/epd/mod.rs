@@ -0,0 +1,404 @@
+mod error;
+mod operand;
+mod suite;
+
+use crate::engine::*;
+use crate::interfaces::*;
+pub use error::*;
+use itertools::Itertools;
+pub use operand::*;
+pub use suite::*;
+
+// https://www.chessprogramming.org/Extended_Position_Description
+
+enum Token {
+    Plain(String),
+    Quoted(String),
+    End,
+}
+
+#[derive(Clone, Debug)]
+pub struct Epd {
+    pub board: Board,
+    // In the order they were read, which is also the order they are written back out in
+    pub operations: Vec<(String, Vec<EpdOperand>)>,
+}
+
+impl Epd {
+    // Opcodes whose operands are moves in SAN, all from the position except pv which is a sequence
+    pub const MOVE_OPCODES: [&'static str; 5] = ["am", "bm", "pm", "pv", "sm"];
+    pub const INTEGER_OPCODES: [&'static str; 8] =
+        ["acd", "acn", "acs", "ce", "dm", "fmvn", "hmvc", "rc"];
+
+    pub fn new(board: Board) -> Self {
+        Epd {
+            board,
+            operations: vec![],
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Epd, EpdError> {
+        let mut rest = line.trim();
+        let mut fields = vec![];
+
+        for _ in 0..4 {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            fields.push(&rest[..end]);
+            rest = rest[end..].trim_start();
+        }
+
+        if fields.iter().any(|field| field.is_empty()) {
+            return Err(EpdError::MissingFields);
+        }
+
+        // SAN operands are resolved by generating moves, which needs a legal position
+        let board = Board::try_from_fen_strict(&fields.join(" ")).map_err(EpdError::InvalidFen)?;
+        let mut epd = Epd::new(board);
+        let mut tokens = Self::tokenize(rest)?.into_iter();
+
+        while let Some(token) = tokens.next() {
+            let opcode = match token {
+                Token::Plain(opcode) if Self::is_opcode(&opcode) => opcode,
+                Token::Plain(opcode) | Token::Quoted(opcode) => {
+                    return Err(EpdError::InvalidOpcode(opcode));
+                }
+                Token::End => continue,
+            };
+
+            let mut operands = vec![];
+            let mut words = vec![];
+            let mut board = epd.board.clone();
+
+            // The semicolon after the last operation is often left out
+            loop {
+                match tokens.next() {
+                    None | Some(Token::End) => break,
+                    Some(Token::Plain(word)) => {
+                        words.push(word.clone());
+                        operands.push(Self::parse_operand(&opcode, word, &mut board)?);
+                    }
+                    Some(Token::Quoted(string)) if Self::MOVE_OPCODES.contains(&&*opcode) => {
+                        return Err(EpdError::InvalidOperand(opcode, string));
+                    }
+                    Some(Token::Quoted(string)) => {
+                        words.push(format!("\"{string}\""));
+                        operands.push(EpdOperand::String(string));
+                    }
+                }
+            }
+
+            if !epd.set_counter(&opcode, &operands) {
+                return Err(EpdError::InvalidOperand(opcode, words.join(" ")));
+            }
+
+            epd.operations.push((opcode, operands));
+        }
+
+        Ok(epd)
+    }
+
+    pub fn to_epd(&self) -> String {
+        let fen = self.board.to_fen();
+        let mut epd = fen.split(' ').take(4).join(" ");
+
+        for (opcode, operands) in &self.operations {
+            let mut board = self.board.clone();
+
+            epd.push(' ');
+            epd.push_str(opcode);
+
+            for operand in operands {
+                epd.push(' ');
+
+                match operand {
+                    EpdOperand::Move(r#move) => {
+                        epd.push_str(&board.move_to_san(*r#move));
+
+                        if opcode == "pv" {
+                            board.make_move(*r#move);
+                        }
+                    }
+                    EpdOperand::Integer(integer) => epd.push_str(&integer.to_string()),
+                    EpdOperand::String(string) => epd.push_str(&format!("\"{string}\"")),
+                    EpdOperand::Symbol(symbol) => epd.push_str(symbol),
+                }
+            }
+
+            epd.push(';');
+        }
+
+        epd
+    }
+
+    pub fn get_operands(&self, opcode: &str) -> Option<&[EpdOperand]> {
+        self.operations
+            .iter()
+            .find(|(name, _)| name == opcode)
+            .map(|(_, operands)| operands.as_slice())
+    }
+
+    pub fn set_operation(&mut self, opcode: &str, operands: Vec<EpdOperand>) {
+        match self.operations.iter_mut().find(|(name, _)| name == opcode) {
+            Some((_, existing)) => *existing = operands,
+            None => self.operations.push((opcode.into(), operands)),
+        }
+    }
+
+    pub fn get_moves(&self, opcode: &str) -> Vec<Move> {
+        self.get_operands(opcode)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|operand| match operand {
+                EpdOperand::Move(r#move) => Some(*r#move),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn get_integer(&self, opcode: &str) -> Option<i64> {
+        match self.get_operands(opcode)? {
+            [EpdOperand::Integer(integer)] => Some(*integer),
+            _ => None,
+        }
+    }
+
+    pub fn get_id(&self) -> Option<&str> {
+        match self.get_operands("id")? {
+            [EpdOperand::String(id) | EpdOperand::Symbol(id)] => Some(id),
+            _ => None,
+        }
+    }
+
+    // Whether a move found by a search solves the position, or None when it has neither bm nor am
+    pub fn check_move(&self, r#move: Option<Move>) -> Option<bool> {
+        let best = self.get_moves("bm");
+        let avoid = self.get_moves("am");
+
+        if best.is_empty() && avoid.is_empty() {
+            return None;
+        }
+
+        Some(r#move.is_some_and(|r#move| {
+            (best.is_empty() || best.contains(&r#move)) && !avoid.contains(&r#move)
+        }))
+    }
+
+    fn tokenize(text: &str) -> Result<Vec<Token>, EpdError> {
+        let mut tokens = vec![];
+        let mut chars = text.chars().peekable();
+
+        while let Some(char) = chars.next() {
+            match char {
+                ';' => tokens.push(Token::End),
+                '"' => {
+                    let mut string = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(char) => string.push(char),
+                            None => return Err(EpdError::UnterminatedString),
+                        }
+                    }
+
+                    tokens.push(Token::Quoted(string));
+                }
+                _ if char.is_whitespace() => {}
+                _ => {
+                    let mut word = String::from(char);
+
+                    while let Some(&char) = chars.peek() {
+                        if char.is_whitespace() || char == ';' || char == '"' {
+                            break;
+                        }
+
+                        word.push(char);
+                        chars.next();
+                    }
+
+                    tokens.push(Token::Plain(word));
+                }
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    // Opcodes start with a letter and have at most fifteen letters, digits or underscores
+    fn is_opcode(word: &str) -> bool {
+        word.len() <= 15
+            && word.starts_with(|char: char| char.is_ascii_alphabetic())
+            && word
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '_')
+    }
+
+    fn parse_operand(
+        opcode: &str,
+        word: String,
+        board: &mut Board,
+    ) -> Result<EpdOperand, EpdError> {
+        if Self::MOVE_OPCODES.contains(&opcode) {
+            let r#move = board
+                .parse_san(&word)
+                .map_err(|error| EpdError::InvalidMove(opcode.into(), error))?;
+
+            if opcode == "pv" {
+                board.make_move(r#move);
+            }
+
+            return Ok(EpdOperand::Move(r#move));
+        }
+
+        match word.parse() {
+            Ok(integer) => Ok(EpdOperand::Integer(integer)),
+            Err(_) if Self::INTEGER_OPCODES.contains(&opcode) => {
+                Err(EpdError::InvalidOperand(opcode.into(), word))
+            }
+            Err(_) => Ok(EpdOperand::Symbol(word)),
+        }
+    }
+
+    // The move counters left out of the position fields may be given as operations instead,
+    // returns false when their operand is not a single number that fits
+    fn set_counter(&mut self, opcode: &str, operands: &[EpdOperand]) -> bool {
+        let state = self.board.states.last_mut().unwrap();
+        let counter = match opcode {
+            "hmvc" => &mut state.halfmove,
+            "fmvn" => &mut state.fullmove,
+            _ => return true,
+        };
+
+        match operands {
+            [EpdOperand::Integer(integer)] => u16::try_from(*integer)
+                .map(|integer| *counter = integer)
+                .is_ok(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::square;
+
+    const WAC_001: &str =
+        r#"2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";"#;
+
+    #[test]
+    fn parse() {
+        let epd = Epd::parse(WAC_001).unwrap();
+
+        assert_eq!(
+            &epd.board,
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+        );
+        assert_eq!(
+            epd.get_moves("bm"),
+            vec![Move::new(square!(G3), square!(G6), MoveFlag::None)]
+        );
+        assert_eq!(epd.get_id(), Some("WAC.001"));
+        assert_eq!(epd.to_epd(), WAC_001);
+    }
+
+    #[test]
+    fn operands() {
+        let epd = Epd::parse(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - \
+             am a4 h4; pv e4 e5 Nf3; acd 12; ce -35; c0 \"a; quoted\" second; hmvc 3; fmvn 9; \
+             id opening",
+        )
+        .unwrap();
+
+        assert_eq!(epd.get_moves("am").len(), 2);
+        assert_eq!(
+            epd.get_moves("pv")[2],
+            Move::new(square!(G1), square!(F3), MoveFlag::None)
+        );
+        assert_eq!(epd.get_integer("acd"), Some(12));
+        assert_eq!(epd.get_integer("ce"), Some(-35));
+        assert_eq!(
+            epd.get_operands("c0"),
+            Some(
+                [
+                    EpdOperand::String("a; quoted".into()),
+                    EpdOperand::Symbol("second".into())
+                ]
+                .as_slice()
+            )
+        );
+        assert_eq!(epd.get_id(), Some("opening"));
+        assert_eq!(epd.get_operands("bm"), None);
+
+        assert_eq!(epd.board.get_state().halfmove, 3);
+        assert_eq!(epd.board.get_state().fullmove, 9);
+
+        assert_eq!(
+            epd.to_epd(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - am a4 h4; pv e4 e5 Nf3; \
+             acd 12; ce -35; c0 \"a; quoted\" second; hmvc 3; fmvn 9; id opening;"
+        );
+    }
+
+    #[test]
+    fn check_move() {
+        let mut epd = Epd::parse("4k3/8/8/8/8/8/8/R3K3 w - - bm Ra8+ Kd2").unwrap();
+
+        let mate = Move::new(square!(A1), square!(A8), MoveFlag::None);
+        let quiet = Move::new(square!(E1), square!(F2), MoveFlag::None);
+
+        assert_eq!(epd.check_move(Some(mate)), Some(true));
+        assert_eq!(epd.check_move(Some(quiet)), Some(false));
+        assert_eq!(epd.check_move(None), Some(false));
+
+        epd.set_operation("bm", vec![]);
+        epd.set_operation("am", vec![EpdOperand::Move(mate)]);
+        assert_eq!(epd.check_move(Some(quiet)), Some(true));
+        assert_eq!(epd.check_move(Some(mate)), Some(false));
+
+        epd.operations.clear();
+        assert_eq!(epd.check_move(Some(mate)), None);
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |epd| Epd::parse(epd).err();
+
+        assert_eq!(
+            parse("4k3/8/8/8/8/8/8/4K3 w -"),
+            Some(EpdError::MissingFields)
+        );
+        assert!(matches!(
+            parse("4k3/8/8/8/8/8/8/4K3 x - -"),
+            Some(EpdError::InvalidFen(_))
+        ));
+        assert!(matches!(
+            parse("8/8/8/8/8/8/8/4K3 b - - bm Kd2;"),
+            Some(EpdError::InvalidFen(_))
+        ));
+        assert_eq!(
+            parse("4k3/8/8/8/8/8/8/4K3 w - - 1bm Kd2;"),
+            Some(EpdError::InvalidOpcode("1bm".into()))
+        );
+        assert_eq!(
+            parse("4k3/8/8/8/8/8/8/4K3 w - - id \"open;"),
+            Some(EpdError::UnterminatedString)
+        );
+        assert_eq!(
+            parse("4k3/8/8/8/8/8/8/4K3 w - - acd deep;"),
+            Some(EpdError::InvalidOperand("acd".into(), "deep".into()))
+        );
+        assert_eq!(
+            parse("4k3/8/8/8/8/8/8/4K3 w - - hmvc 1 2;"),
+            Some(EpdError::InvalidOperand("hmvc".into(), "1 2".into()))
+        );
+        assert_eq!(
+            parse("4k3/8/8/8/8/8/8/4K3 w - - bm Ke3;"),
+            Some(EpdError::InvalidMove(
+                "bm".into(),
+                SanError::Illegal("Ke3".into())
+            ))
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use super::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EpdOperand {
+    // Operands of am, bm, pm, pv and sm, written in SAN
+    Move(Move),
+    Integer(i64),
+    // Operands that were written between double quotes
+    String(String),
+    // Any other unquoted operand
+    Symbol(String),
+}
@@ -0,0 +1,140 @@
+use super::*;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+// Test suites such as WAC and STS, one EPD per line with the expected moves in bm or am
+pub struct EpdSuite {
+    pub positions: Vec<Epd>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EpdTestResult {
+    // None for positions without a bm or am to check against
+    pub passed: Option<bool>,
+    pub search: SearchResult,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EpdSuiteResult {
+    pub passed: usize,
+    // Positions that have a bm or am to check against
+    pub total: usize,
+    pub nodes: u64,
+    pub time: Duration,
+}
+
+impl EpdSuite {
+    // Blank lines and lines starting with # are skipped
+    pub fn parse(text: &str) -> Result<Self, EpdError> {
+        let positions = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'))
+            .map(|(index, line)| {
+                Epd::parse(line).map_err(|error| EpdError::AtLine(index + 1, Box::new(error)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(EpdSuite { positions })
+    }
+
+    // Every position is searched from an empty table so that results do not depend on the order
+    pub fn run(
+        &self,
+        limits: &SearchLimits,
+        table: Arc<TranspositionTable>,
+        mut report: impl FnMut(&Epd, &EpdTestResult),
+    ) -> EpdSuiteResult {
+        let start = Instant::now();
+        let mut result = EpdSuiteResult::default();
+
+        for epd in &self.positions {
+            table.clear();
+
+            let search = Search::new(
+                epd.board.clone(),
+                limits.clone(),
+                table.clone(),
+                Arc::new(AtomicBool::new(false)),
+            )
+            .run(|_| {});
+
+            let test = EpdTestResult {
+                passed: epd.check_move(search.best_move),
+                search,
+            };
+
+            if let Some(passed) = test.passed {
+                result.total += 1;
+                result.passed += passed as usize;
+            }
+
+            result.nodes += test.search.nodes;
+            report(epd, &test);
+        }
+
+        result.time = start.elapsed();
+        result
+    }
+}
+
+impl EpdSuiteResult {
+    pub fn get_nps(&self) -> u64 {
+        self.nodes * 1000 / (self.time.as_millis() as u64).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUITE: &str = r#"# Mates in one and a queen that must not be taken
+6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id "back rank";
+
+k7/8/2K5/8/8/8/8/1Q6 w - - am Qb5; bm Qb7#; id "corner";
+4k3/8/8/8/8/2q5/3P4/4K3 w - - am dxc3; id "poisoned";
+4k3/8/8/8/8/8/8/4K3 w - - id "no expectation";
+"#;
+
+    #[test]
+    fn run() {
+        let suite = EpdSuite::parse(SUITE).unwrap();
+        assert_eq!(suite.positions.len(), 4);
+
+        let limits = SearchLimits {
+            depth: Some(3),
+            ..Default::default()
+        };
+
+        let mut reported = vec![];
+        let result = suite.run(
+            &limits,
+            Arc::new(TranspositionTable::new(1)),
+            |epd, test| {
+                reported.push((epd.get_id().unwrap().to_string(), test.passed));
+            },
+        );
+
+        assert_eq!(result.total, 3);
+        assert_eq!(result.passed, 2);
+        assert!(result.nodes > 0);
+        assert_eq!(
+            reported,
+            vec![
+                ("back rank".into(), Some(true)),
+                ("corner".into(), Some(true)),
+                ("poisoned".into(), Some(false)),
+                ("no expectation".into(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_numbers() {
+        assert!(matches!(
+            EpdSuite::parse("\n4k3/8/8/8/8/8/8/4K3 w - - id \"ok\";\n4k3/8 w - -\n"),
+            Err(EpdError::AtLine(3, _))
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::engine::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FenErrorKind {
+    RankTooWide(u8),
+    RankTooNarrow(u8),
+    TooManyRanks,
+    TooFewRanks,
+    DuplicateKing(PieceColor),
+    InvalidPiece(char),
+    MissingActiveColor,
+    InvalidActiveColor(char),
+    MissingCastlingRights,
+    InvalidCastlingRights(char),
+    MissingEnPassant,
+    InvalidEnPassant(String),
+    InvalidHalfMoveClock(String),
+    InvalidFullMoveNumber(String),
+    Incomplete,
+    TrailingData,
+    IllegalPosition(Vec<Violation>),
+}
+
+// Column is the offset in characters into the FEN string where the problem was detected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FenError {
+    pub kind: FenErrorKind,
+    pub column: usize,
+}
+
+impl FenError {
+    pub fn new(kind: FenErrorKind, column: usize) -> Self {
+        FenError { kind, column }
+    }
+}
+
+impl std::fmt::Display for FenErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use FenErrorKind::*;
+
+        match self {
+            RankTooWide(rank) => write!(f, "Invalid piece placement: Rank {rank} exceeds 8 files"),
+            RankTooNarrow(rank) => write!(
+                f,
+                "Invalid piece placement: Rank {rank} does not contain 8 files"
+            ),
+            TooManyRanks => write!(f, "Invalid piece placement: More than 8 ranks"),
+            TooFewRanks => write!(
+                f,
+                "Invalid piece placement: Not all ranks and files have been filled up"
+            ),
+            DuplicateKing(color) => write!(
+                f,
+                "Invalid piece placement: {} King already exists",
+                match color {
+                    PieceColor::White => "White",
+                    PieceColor::Black => "Black",
+                }
+            ),
+            InvalidPiece(char) => write!(f, "Invalid piece placement: Unknown character {char}"),
+            MissingActiveColor => write!(f, "Unset active color"),
+            InvalidActiveColor(char) => write!(f, "Invalid active color: Unknown character {char}"),
+            MissingCastlingRights => {
+                write!(f, "Invalid castling rights: No castling rights provided")
+            }
+            InvalidCastlingRights(char) => {
+                write!(f, "Invalid castling rights: Unknown character {char}")
+            }
+            MissingEnPassant => write!(
+                f,
+                "Invalid possible en passant targets: No possible en passant target provided"
+            ),
+            InvalidEnPassant(string) => write!(
+                f,
+                "Invalid possible en passant targets: Unknown square {string}"
+            ),
+            InvalidHalfMoveClock(string) => {
+                write!(f, "Invalid half move clock: Invalid number {string}")
+            }
+            InvalidFullMoveNumber(string) => {
+                write!(f, "Invalid full move number: Invalid number {string}")
+            }
+            Incomplete => write!(f, "Invalid FEN: Missing fields before the move counters"),
+            TrailingData => write!(
+                f,
+                "Invalid FEN: Extra characters provided at the end of the string"
+            ),
+            IllegalPosition(violations) => write!(f, "Illegal position: {violations:?}"),
+        }
+    }
+}
+
+impl std::fmt::Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (column {})", self.kind, self.column)
+    }
+}
+
+impl std::error::Error for FenError {}
@@ -0,0 +1,709 @@
+mod error;
+
+use crate::engine::*;
+use FenErrorKind::*;
+use FenSection::*;
+pub use error::*;
+use itertools::Itertools;
+
+// https://www.chess.com/terms/fen-chess
+
+enum FenSection {
+    PiecePlacement(i8, i8),
+    ActiveColor(bool),
+    CastlingRights(bool),
+    PossibleEnPassantTargets(String),
+    HalfMoveClock(String),
+    FullMoveNumber(String),
+    Finished,
+}
+
+impl Board {
+    pub fn from_fen(fen: &str) -> Board {
+        Board::try_from_fen(fen).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    // Also rejects positions that parse fine but could never occur in a game
+    pub fn try_from_fen_strict(fen: &str) -> Result<Board, FenError> {
+        let board = Board::try_from_fen(fen)?;
+        let violations = board.validate();
+
+        if !violations.is_empty() {
+            return Err(FenError::new(IllegalPosition(violations), 0));
+        }
+
+        Ok(board)
+    }
+
+    // The halfmove clock and fullmove number may be left out, as in EPD
+    pub fn try_from_fen(fen: &str) -> Result<Board, FenError> {
+        let mut board = Board::new();
+        let mut state = BoardState::new();
+        let mut section = PiecePlacement(7, 0);
+
+        let offset = fen.chars().count() - fen.trim_start().chars().count();
+        let error = |kind: FenErrorKind, column: usize| Err(FenError::new(kind, offset + column));
+
+        // The trailing space finishes the last section the same way a separator would
+        for (column, char) in fen.trim().chars().chain([' ']).enumerate() {
+            match &mut section {
+                PiecePlacement(rank, file) => {
+                    if char.is_ascii_digit() {
+                        let number = char.to_digit(10).unwrap() as i8;
+                        if number == 0 {
+                            return error(InvalidPiece(char), column);
+                        }
+
+                        if number + *file > 8 {
+                            return error(RankTooWide(*rank as u8 + 1), column);
+                        }
+
+                        *file += number;
+
+                        continue;
+                    }
+
+                    if char.is_alphabetic() {
+                        if *file == 8 {
+                            return error(RankTooWide(*rank as u8 + 1), column);
+                        }
+
+                        let square = (*rank * 8 + *file) as u8;
+
+                        let piece = match char {
+                            'K' => WHITE_KING,
+                            'Q' => WHITE_QUEEN,
+                            'R' => WHITE_ROOK,
+                            'B' => WHITE_BISHOP,
+                            'N' => WHITE_KNIGHT,
+                            'P' => WHITE_PAWN,
+                            'k' => BLACK_KING,
+                            'q' => BLACK_QUEEN,
+                            'r' => BLACK_ROOK,
+                            'b' => BLACK_BISHOP,
+                            'n' => BLACK_KNIGHT,
+                            'p' => BLACK_PAWN,
+                            _ => return error(InvalidPiece(char), column),
+                        };
+
+                        if piece.get_type() == PieceType::King && board.pieces[piece].is_some() {
+                            return error(DuplicateKing(piece.get_color()), column);
+                        }
+
+                        board.set_square(square, piece);
+
+                        *file += 1;
+
+                        continue;
+                    }
+
+                    if char == '/' {
+                        if *file != 8 {
+                            return error(RankTooNarrow(*rank as u8 + 1), column);
+                        }
+
+                        if *rank == 0 {
+                            return error(TooManyRanks, column);
+                        }
+
+                        *rank -= 1;
+                        *file = 0;
+
+                        continue;
+                    }
+
+                    if char == ' ' {
+                        if *rank != 0 || *file != 8 {
+                            return error(TooFewRanks, column);
+                        }
+
+                        section = ActiveColor(false);
+
+                        continue;
+                    }
+
+                    return error(InvalidPiece(char), column);
+                }
+                ActiveColor(is_set) => {
+                    match char {
+                        'w' if !*is_set => board.turn = PieceColor::White,
+                        'b' if !*is_set => board.turn = PieceColor::Black,
+                        ' ' => {
+                            if !*is_set {
+                                return error(MissingActiveColor, column);
+                            }
+
+                            section = CastlingRights(false);
+
+                            continue;
+                        }
+                        _ => return error(InvalidActiveColor(char), column),
+                    }
+
+                    section = ActiveColor(true)
+                }
+                CastlingRights(is_set) => {
+                    // Nothing may follow a -
+                    if *is_set && state.castling == [None; 4] && char != ' ' {
+                        return error(InvalidCastlingRights(char), column);
+                    }
+
+                    match char {
+                        'K' => {
+                            state.castling[WHITE_KING] = Some(board.find_castling_file(WHITE_KING))
+                        }
+                        'Q' => {
+                            state.castling[WHITE_QUEEN] =
+                                Some(board.find_castling_file(WHITE_QUEEN))
+                        }
+                        'k' => {
+                            state.castling[BLACK_KING] = Some(board.find_castling_file(BLACK_KING))
+                        }
+                        'q' => {
+                            state.castling[BLACK_QUEEN] =
+                                Some(board.find_castling_file(BLACK_QUEEN))
+                        }
+                        // Shredder-FEN names the file of the rook instead
+                        'A'..='H' | 'a'..='h' => {
+                            let castle = board.get_castle_of_file(char);
+                            state.castling[castle] = Some(char.to_ascii_lowercase() as u8 - b'a');
+                            board.chess960 = true;
+                        }
+                        '-' if !*is_set => {}
+                        ' ' => {
+                            if !*is_set {
+                                return error(MissingCastlingRights, column);
+                            }
+
+                            section = PossibleEnPassantTargets("".into());
+
+                            continue;
+                        }
+                        _ => return error(InvalidCastlingRights(char), column),
+                    }
+
+                    section = CastlingRights(true)
+                }
+                PossibleEnPassantTargets(string) => {
+                    if char != ' ' {
+                        section = PossibleEnPassantTargets(format!("{string}{char}"));
+
+                        continue;
+                    }
+
+                    if string.is_empty() {
+                        return error(MissingEnPassant, column);
+                    }
+
+                    if string == "-" {
+                        section = HalfMoveClock("".into());
+
+                        continue;
+                    }
+
+                    let files = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+                    let ranks = ['3', '6'];
+
+                    let mut chars = string.chars();
+                    let file = chars.next().unwrap();
+                    let rank = chars.next().unwrap_or(' ');
+
+                    if string.len() == 2 && files.contains(&file) && ranks.contains(&rank) {
+                        let rank = rank.to_digit(10).unwrap() as u8;
+                        let file = files.iter().position(|r| *r == file).unwrap() as u8;
+
+                        state.enpassant = Bitboard::from(((rank - 1) * 8) + file);
+
+                        section = HalfMoveClock("".into());
+                    } else {
+                        let start = column - string.chars().count();
+                        return error(InvalidEnPassant(string.clone()), start);
+                    }
+                }
+                HalfMoveClock(string) => {
+                    if char != ' ' {
+                        section = HalfMoveClock(format!("{string}{char}"));
+
+                        continue;
+                    }
+
+                    let start = column - string.chars().count();
+                    match string.parse::<u16>() {
+                        Ok(number) => {
+                            state.halfmove = number;
+
+                            section = FullMoveNumber("".into());
+
+                            continue;
+                        }
+                        Err(_) => return error(InvalidHalfMoveClock(string.clone()), start),
+                    }
+                }
+                FullMoveNumber(string) => {
+                    if char != ' ' {
+                        section = FullMoveNumber(format!("{string}{char}"));
+
+                        continue;
+                    }
+
+                    let start = column - string.chars().count();
+                    match string.parse::<u16>() {
+                        Ok(number) => {
+                            state.fullmove = number;
+
+                            section = Finished;
+
+                            continue;
+                        }
+                        Err(_) => return error(InvalidFullMoveNumber(string.clone()), start),
+                    }
+                }
+                Finished => return error(TrailingData, column),
+            }
+        }
+
+        // Only the move counters are optional
+        if !matches!(section, HalfMoveClock(_) | FullMoveNumber(_) | Finished) {
+            return error(Incomplete, fen.trim().chars().count());
+        }
+
+        // Rights that standard chess could not have, given in X-FEN with K and Q
+        for castle in [WHITE_KING, WHITE_QUEEN, BLACK_KING, BLACK_QUEEN] {
+            if let Some(file) = state.castling[castle] {
+                let king = board.get_king_file(castle.get_color());
+                board.chess960 |= !matches!(file, 0 | 7) || king.is_some_and(|king| king != 4);
+            }
+        }
+
+        board.states.push(state);
+
+        let key = board.calculate_key();
+        board.states.last_mut().unwrap().key = key;
+
+        board.update_checkers_and_pinned();
+
+        Ok(board)
+    }
+
+    // Castling rights are written in X-FEN, which only differs from standard FEN in Chess960 when
+    // the king could castle with more than one rook on the same side
+    pub fn to_fen(&self) -> String {
+        self.write_fen(false)
+    }
+
+    // Castling rights are written as the files of the rooks, such as HAha for the initial position
+    pub fn to_shredder_fen(&self) -> String {
+        self.write_fen(true)
+    }
+
+    fn write_fen(&self, shredder: bool) -> String {
+        let state = self.get_state();
+
+        let mut fen = String::new();
+        let mut stack = 0;
+
+        for rank in (0..8u8).rev() {
+            for file in 0..8u8 {
+                let index = (rank * 8) + file;
+
+                if let Some(piece) = self.squares[index as usize] {
+                    if stack > 0 {
+                        fen.push_str(&format!("{stack}"));
+                        stack = 0;
+                    }
+
+                    match piece {
+                        WHITE_KING => fen.push('K'),
+                        WHITE_QUEEN => fen.push('Q'),
+                        WHITE_ROOK => fen.push('R'),
+                        WHITE_BISHOP => fen.push('B'),
+                        WHITE_KNIGHT => fen.push('N'),
+                        WHITE_PAWN => fen.push('P'),
+                        BLACK_KING => fen.push('k'),
+                        BLACK_QUEEN => fen.push('q'),
+                        BLACK_ROOK => fen.push('r'),
+                        BLACK_BISHOP => fen.push('b'),
+                        BLACK_KNIGHT => fen.push('n'),
+                        BLACK_PAWN => fen.push('p'),
+                        _ => unreachable!(),
+                    }
+                } else {
+                    stack += 1;
+                }
+            }
+
+            if stack > 0 {
+                fen.push_str(&format!("{stack}"));
+                stack = 0;
+            }
+
+            fen.push('/');
+        }
+
+        fen.pop();
+        fen.push(' ');
+
+        match self.turn {
+            PieceColor::White => fen.push('w'),
+            PieceColor::Black => fen.push('b'),
+        }
+
+        fen.push(' ');
+
+        for castle in [WHITE_KING, WHITE_QUEEN, BLACK_KING, BLACK_QUEEN] {
+            if let Some(file) = state.castling[castle] {
+                fen.push(self.get_castling_char(castle, file, shredder));
+            }
+        }
+
+        if state.castling == [None; 4] {
+            fen.push('-');
+        }
+
+        fen.push(' ');
+
+        if state.enpassant.is_some() {
+            let square = state.enpassant.into_iter().find_or_first(|_| true).unwrap();
+            fen.push_str(&format!(
+                "{}{}",
+                ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'][square as usize & 7],
+                ['1', '2', '3', '4', '5', '6', '7', '8'][square as usize >> 3]
+            ))
+        } else {
+            fen.push('-');
+        }
+
+        fen.push(' ');
+
+        fen.push_str(&format!("{}", state.halfmove));
+
+        fen.push(' ');
+
+        fen.push_str(&format!("{}", state.fullmove));
+
+        fen
+    }
+}
+
+impl Board {
+    fn get_king_file(&self, color: PieceColor) -> Option<u8> {
+        self.pieces[color | PieceType::King]
+            .into_iter()
+            .find(|square| square >> 3 == color.get_home_row())
+            .map(|square| square & 7)
+    }
+
+    // X-FEN castles with the outermost rook on that side of the king. Without one, the corner is
+    // assumed so that validation can point out the missing rook
+    fn find_castling_file(&self, castle: Piece) -> u8 {
+        let color = castle.get_color();
+        let row = color.get_home_row();
+        let king = self.get_king_file(color);
+
+        let (files, corner) = match castle.get_type() {
+            PieceType::King => ((king.map_or(0, |king| king + 1)..8).rev().collect(), 7),
+            _ => ((0..king.unwrap_or(8)).collect::<Vec<_>>(), 0),
+        };
+
+        files
+            .into_iter()
+            .find(|file| self.squares[(row * 8 + file) as usize] == Some(color | PieceType::Rook))
+            .unwrap_or(corner)
+    }
+
+    // Shredder-FEN tells the sides apart by whether the rook is on the left or right of the king
+    fn get_castle_of_file(&self, char: char) -> Piece {
+        let color = if char.is_ascii_uppercase() {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+
+        let file = char.to_ascii_lowercase() as u8 - b'a';
+        let king = self.get_king_file(color).unwrap_or(4);
+
+        if file > king {
+            color | PieceType::King
+        } else {
+            color | PieceType::Queen
+        }
+    }
+
+    fn get_castling_char(&self, castle: Piece, file: u8, shredder: bool) -> char {
+        let color = castle.get_color();
+        let row = color.get_home_row();
+
+        // Another rook further out means K or Q would refer to that one instead
+        let outer_files = match castle.get_type() {
+            PieceType::King => file + 1..8,
+            _ => 0..file,
+        };
+        let is_outermost = outer_files
+            .into_iter()
+            .all(|file| self.squares[(row * 8 + file) as usize] != Some(color | PieceType::Rook));
+
+        let char = match castle.get_type() {
+            _ if shredder || !is_outermost => (b'A' + file) as char,
+            PieceType::King => 'K',
+            _ => 'Q',
+        };
+
+        match color {
+            PieceColor::White => char,
+            PieceColor::Black => char.to_ascii_lowercase(),
+        }
+    }
+}
+
+impl PartialEq<str> for Board {
+    fn eq(&self, other: &str) -> bool {
+        self.to_fen() == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(fen: &str) {
+        assert_eq!(Board::from_fen(fen).to_fen(), fen);
+    }
+
+    #[test]
+    fn counters() {
+        round_trip("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        round_trip("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8");
+        round_trip("8/8/4k3/8/8/3K4/8/8 b - - 99 256");
+        round_trip("8/8/4k3/8/8/3K4/8/8 w - - 1234 5678");
+    }
+
+    #[test]
+    fn default_board() {
+        assert_eq!(
+            Board::default().to_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn missing_counters() {
+        let board = Board::try_from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+        assert_eq!(&board, "4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+
+        let board = Board::try_from_fen("4k3/8/8/8/8/8/8/4K3 b - - 12").unwrap();
+        assert_eq!(&board, "4k3/8/8/8/8/8/8/4K3 b - - 12 1");
+    }
+
+    fn assert_error(fen: &str, kind: FenErrorKind, column: usize) {
+        let error = Board::try_from_fen(fen).err();
+
+        assert_eq!(error, Some(FenError::new(kind, column)), "{fen}");
+    }
+
+    #[test]
+    fn errors() {
+        assert_error("4k4/8/8/8/8/8/8/4K3 w - - 0 1", RankTooWide(8), 2);
+        assert_error("4k3p/8/8/8/8/8/8/4K3 w - - 0 1", RankTooWide(8), 3);
+        assert_error("4k2/8/8/8/8/8/8/4K3 w - - 0 1", RankTooNarrow(8), 3);
+        assert_error("4k3/8/8/8/8/8/8/4K3/8 w - - 0 1", TooManyRanks, 19);
+        assert_error("4k3/8/8/8/8/8/4K3 w - - 0 1", TooFewRanks, 17);
+        assert_error(
+            "4k3/8/8/8/8/8/8/3KK3 w - - 0 1",
+            DuplicateKing(PieceColor::White),
+            18,
+        );
+        assert_error("4k3/8/8/8/8/8/8/4K2x w - - 0 1", InvalidPiece('x'), 19);
+        assert_error("4k3/8/8/8/8/8/8/4K3  - - 0 1", MissingActiveColor, 20);
+        assert_error("4k3/8/8/8/8/8/8/4K3 x - - 0 1", InvalidActiveColor('x'), 20);
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 wb - - 0 1",
+            InvalidActiveColor('b'),
+            21,
+        );
+        assert_error("4k3/8/8/8/8/8/8/4K3 w  - 0 1", MissingCastlingRights, 22);
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w KX - 0 1",
+            InvalidCastlingRights('X'),
+            23,
+        );
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w -K - 0 1",
+            InvalidCastlingRights('K'),
+            23,
+        );
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w K- - 0 1",
+            InvalidCastlingRights('-'),
+            23,
+        );
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w - e9 0 1",
+            InvalidEnPassant("e9".into()),
+            24,
+        );
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w - e4 0 1",
+            InvalidEnPassant("e4".into()),
+            24,
+        );
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w - - x 1",
+            InvalidHalfMoveClock("x".into()),
+            26,
+        );
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w - - -1 1",
+            InvalidHalfMoveClock("-1".into()),
+            26,
+        );
+        assert_error(
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 99999",
+            InvalidFullMoveNumber("99999".into()),
+            28,
+        );
+        assert_error("4k3/8/8/8/8/8/8/4K3 w - - 0 1 x", TrailingData, 30);
+        assert_error("4k3/8/8/8/8/8/8/4K3 w", Incomplete, 21);
+        assert_error("4k3/8/8/8/8/8/8/4K3 w -", Incomplete, 23);
+    }
+
+    #[test]
+    fn strict() {
+        assert!(
+            Board::try_from_fen_strict("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+                .is_ok()
+        );
+
+        let fen = "rnbq1bnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert!(Board::try_from_fen(fen).is_ok());
+        assert_eq!(
+            Board::try_from_fen_strict(fen).err(),
+            Some(FenError::new(
+                IllegalPosition(vec![
+                    Violation::MissingKing(PieceColor::Black),
+                    Violation::InvalidCastlingRights(BLACK_KING),
+                    Violation::InvalidCastlingRights(BLACK_QUEEN),
+                ]),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn error_column_counts_leading_whitespace() {
+        let error = Board::try_from_fen("  4k3/8/8/8/8/8/8/4K2x w - - 0 1")
+            .err()
+            .unwrap();
+
+        assert_eq!(error, FenError::new(InvalidPiece('x'), 21));
+        assert_eq!(
+            error.to_string(),
+            "Invalid piece placement: Unknown character x (column 21)"
+        );
+    }
+
+    #[test]
+    fn enpassant_at_end() {
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3");
+
+        assert_eq!(
+            &board,
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+    }
+
+    #[test]
+    fn chess960() {
+        let board = Board::initial();
+        assert!(!board.chess960);
+        assert_eq!(
+            board.to_shredder_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1"
+        );
+
+        let fen = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+        let board = Board::from_fen(fen);
+        assert!(board.chess960);
+        assert_eq!(
+            board.get_state().castling,
+            [Some(7), Some(5), Some(7), Some(5)]
+        );
+        assert_eq!(board.to_shredder_fen(), fen);
+        assert_eq!(
+            &board,
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9"
+        );
+        round_trip(&board.to_fen());
+
+        // X-FEN can only use K and Q for the outermost rooks
+        let fen = "rk4rr/8/8/8/8/8/8/RK4RR w GAga - 0 1";
+        let board = Board::from_fen(fen);
+        assert_eq!(
+            board.get_state().castling,
+            [Some(6), Some(0), Some(6), Some(0)]
+        );
+        assert_eq!(&board, "rk4rr/8/8/8/8/8/8/RK4RR w GQgq - 0 1");
+        assert_eq!(Board::from_fen(&board.to_fen()).to_shredder_fen(), fen);
+    }
+
+    // Morphy vs Duke Karl / Count Isouard, Paris 1858
+    #[test]
+    fn game() {
+        let mut board = Board::initial();
+
+        let moves = [
+            "e2e4", "e7e5", "g1f3", "d7d6", "d2d4", "c8g4", "d4e5", "g4f3", "d1f3", "d6e5", "f1c4",
+            "g8f6", "f3b3", "d8e7", "b1c3", "c7c6", "c1g5", "b7b5", "c3b5", "c6b5", "c4b5", "b8d7",
+            "e1c1", "a8d8", "d1d7", "d8d7", "h1d1", "e7e6", "b5d7", "f6d7", "b3b8", "d7b8", "d1d8",
+        ];
+
+        for text in moves {
+            let r#move = board.parse_uci_move(text).unwrap();
+
+            board.make_move(r#move);
+            round_trip(&board.to_fen());
+        }
+
+        assert_eq!(
+            &board,
+            "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17"
+        );
+    }
+
+    mod random {
+        use super::*;
+        use proptest::prelude::*;
+
+        // Every field drawn from the characters it may contain, so that most inputs get past the
+        // first error and into the later sections
+        const FEN_LIKE: &str = concat!(
+            "[1-9pnbrqkPNBRQKx/]{0,72} [wbx]{0,2} [KQkqA-Ha-h-]{0,5} [a-h1-8-]{0,3} ",
+            "[0-9x-]{0,4} [0-9x-]{0,4}"
+        );
+
+        // Parsing never panics, and whatever passes validation can be played on
+        fn check(fen: &str) {
+            let _ = Board::try_from_fen(fen);
+
+            if let Ok(mut board) = Board::try_from_fen_strict(fen) {
+                round_trip(&board.to_fen());
+
+                for r#move in board.calculate_moves() {
+                    board.make_move(r#move);
+                    board.undo_move(r#move);
+                }
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn arbitrary_input(fen in any::<String>()) {
+                check(&fen);
+            }
+
+            #[test]
+            fn fen_like_input(fen in FEN_LIKE) {
+                check(&fen);
+            }
+        }
+    }
+}
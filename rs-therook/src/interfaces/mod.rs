@@ -1,4 +1,17 @@
+mod epd;
 mod fen;
+mod pgn;
+mod san;
+#[cfg(feature = "stockfish")]
 mod stockfish;
+mod uci;
+mod uci_move;
 
+pub use epd::*;
+pub use fen::*;
+pub use pgn::*;
+pub use san::*;
+#[cfg(feature = "stockfish")]
 pub use stockfish::*;
+pub use uci::*;
+pub use uci_move::*;
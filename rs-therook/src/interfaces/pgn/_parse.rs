@@ -0,0 +1,200 @@
+use super::*;
+
+enum Token {
+    Comment(String),
+    Open,
+    Close,
+    Nag(u8),
+    Move(String, Vec<u8>),
+    Result(PgnResult),
+}
+
+impl PgnGame {
+    // Parses a single game, use PgnReader to go through a file of them
+    pub fn parse(text: &str) -> Result<PgnGame, PgnError> {
+        let mut game = PgnGame::default();
+        let mut movetext = String::new();
+
+        for line in text.lines() {
+            let trimmed = line.trim();
+
+            if movetext.trim().is_empty() && trimmed.starts_with('[') {
+                let (name, value) =
+                    Self::parse_tag(trimmed).ok_or_else(|| PgnError::InvalidTag(trimmed.into()))?;
+                game.tags.push((name, value));
+            } else if !line.starts_with('%') {
+                movetext.push_str(line);
+                movetext.push('\n');
+            }
+        }
+
+        let mut board = game.get_initial_board().map_err(PgnError::InvalidFen)?;
+        let mut tokens = Self::tokenize(&movetext)?.into_iter();
+
+        let (line, result) = Self::parse_line(&mut board, &mut tokens, false)?;
+
+        game.comments = line.comments;
+        game.moves = line.moves;
+        game.result = result
+            .or_else(|| game.get_tag("Result").and_then(PgnResult::parse))
+            .unwrap_or_default();
+
+        Ok(game)
+    }
+
+    fn parse_tag(line: &str) -> Option<(String, String)> {
+        let inner = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+        let (name, value) = inner.split_once(char::is_whitespace)?;
+        let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+        let mut unescaped = String::new();
+        let mut chars = value.chars();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '\\' => unescaped.push(chars.next()?),
+                _ => unescaped.push(char),
+            }
+        }
+
+        Some((name.into(), unescaped))
+    }
+
+    fn tokenize(movetext: &str) -> Result<Vec<Token>, PgnError> {
+        let mut tokens = vec![];
+        let mut chars = movetext.chars().peekable();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '{' => {
+                    let mut comment = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(char) => comment.push(char),
+                            None => return Err(PgnError::UnterminatedComment),
+                        }
+                    }
+
+                    tokens.push(Token::Comment(
+                        comment.split_whitespace().collect::<Vec<_>>().join(" "),
+                    ));
+                }
+                ';' => {
+                    let comment = chars.by_ref().take_while(|&char| char != '\n');
+                    tokens.push(Token::Comment(comment.collect::<String>().trim().into()));
+                }
+                '(' => tokens.push(Token::Open),
+                ')' => tokens.push(Token::Close),
+                _ if char.is_whitespace() => {}
+                _ => {
+                    let mut word = String::from(char);
+
+                    while let Some(&char) = chars.peek() {
+                        if char.is_whitespace() || "{}();$".contains(char) {
+                            break;
+                        }
+
+                        word.push(char);
+                        chars.next();
+                    }
+
+                    if let Some(nag) = word.strip_prefix('$') {
+                        // Unknown glyphs are kept out rather than failing the whole game
+                        if let Ok(nag) = nag.parse() {
+                            tokens.push(Token::Nag(nag));
+                        }
+                    } else if let Some(result) = PgnResult::parse(&word) {
+                        tokens.push(Token::Result(result));
+                    } else if let Some(token) = Self::parse_move_word(&word) {
+                        tokens.push(token);
+                    }
+                }
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    // Splits off move numbers such as 12. or 12... and suffix annotations such as !? from the SAN
+    fn parse_move_word(word: &str) -> Option<Token> {
+        let digits = word.trim_start_matches(|char: char| char.is_ascii_digit());
+        let san = match digits.strip_prefix('.') {
+            Some(rest) => rest.trim_start_matches('.'),
+            None if digits.is_empty() => "",
+            None => word,
+        };
+
+        if san.is_empty() {
+            return None;
+        }
+
+        let annotation_start = san.find(['!', '?']).unwrap_or(san.len());
+        let (san, annotation) = san.split_at(annotation_start);
+
+        let nags = match annotation {
+            "" => vec![],
+            "!" => vec![1],
+            "?" => vec![2],
+            "!!" => vec![3],
+            "??" => vec![4],
+            "!?" => vec![5],
+            "?!" => vec![6],
+            _ => vec![],
+        };
+
+        Some(Token::Move(san.into(), nags))
+    }
+
+    fn parse_line(
+        board: &mut Board,
+        tokens: &mut impl Iterator<Item = Token>,
+        nested: bool,
+    ) -> Result<(PgnVariation, Option<PgnResult>), PgnError> {
+        let mut line = PgnVariation::default();
+
+        loop {
+            match tokens.next() {
+                None if nested => return Err(PgnError::UnmatchedParenthesis),
+                None => return Ok((line, None)),
+                Some(Token::Close) if nested => return Ok((line, None)),
+                Some(Token::Close) => return Err(PgnError::UnmatchedParenthesis),
+                Some(Token::Result(result)) if !nested => return Ok((line, Some(result))),
+                Some(Token::Result(_)) => {}
+                Some(Token::Comment(comment)) => match line.moves.last_mut() {
+                    Some(r#move) => r#move.comments.push(comment),
+                    None => line.comments.push(comment),
+                },
+                Some(Token::Nag(nag)) => {
+                    if let Some(r#move) = line.moves.last_mut() {
+                        r#move.nags.push(nag);
+                    }
+                }
+                Some(Token::Open) => {
+                    let Some(last) = line.moves.last_mut() else {
+                        return Err(PgnError::VariationWithoutMove);
+                    };
+
+                    // The variation replaces the last move, so it starts from the position before it
+                    let mut variation_board = board.clone();
+                    variation_board.undo_move(last.r#move);
+
+                    let (variation, _) = Self::parse_line(&mut variation_board, tokens, true)?;
+                    last.variations.push(variation);
+                }
+                Some(Token::Move(san, nags)) => {
+                    let r#move = board
+                        .parse_san(&san)
+                        .map_err(|error| PgnError::InvalidMove(line.moves.len() + 1, error))?;
+                    board.make_move(r#move);
+
+                    line.moves.push(PgnMove {
+                        nags,
+                        ..PgnMove::new(r#move)
+                    });
+                }
+            }
+        }
+    }
+}
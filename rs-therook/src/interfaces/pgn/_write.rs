@@ -0,0 +1,130 @@
+use super::*;
+
+impl PgnGame {
+    // Export lines are kept below 80 characters as the standard asks
+    pub const LINE_WIDTH: usize = 79;
+
+    pub fn to_pgn(&self) -> Result<String, FenError> {
+        let mut pgn = String::new();
+
+        for name in Self::SEVEN_TAG_ROSTER {
+            let value = match name {
+                "Result" => self.result.to_string(),
+                "Date" => self.get_tag(name).unwrap_or("????.??.??").into(),
+                _ => self.get_tag(name).unwrap_or("?").into(),
+            };
+
+            pgn.push_str(&Self::format_tag(name, &value));
+        }
+
+        for (name, value) in &self.tags {
+            if !Self::SEVEN_TAG_ROSTER.contains(&name.as_str()) {
+                pgn.push_str(&Self::format_tag(name, value));
+            }
+        }
+
+        pgn.push('\n');
+
+        let mut words = vec![];
+        let mut board = self.get_initial_board()?;
+
+        Self::write_line(&mut board, &self.comments, &self.moves, &mut words);
+        words.push(self.result.to_string());
+
+        let mut line = String::new();
+
+        for word in Self::attach_parentheses(words) {
+            if !line.is_empty() && line.len() + 1 + word.len() > Self::LINE_WIDTH {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(&word);
+        }
+
+        pgn.push_str(&line);
+        pgn.push_str("\n\n");
+
+        Ok(pgn)
+    }
+
+    // Parentheses hug the moves they enclose and must never be wrapped onto a line by themselves
+    fn attach_parentheses(words: Vec<String>) -> Vec<String> {
+        let mut attached: Vec<String> = vec![];
+        let mut open = String::new();
+
+        for word in words {
+            match word.as_str() {
+                "(" => open.push('('),
+                ")" => match attached.last_mut() {
+                    Some(last) if open.is_empty() => last.push(')'),
+                    _ => open.push(')'),
+                },
+                _ => attached.push(std::mem::take(&mut open) + &word),
+            }
+        }
+
+        attached
+    }
+
+    fn format_tag(name: &str, value: &str) -> String {
+        let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("[{name} \"{value}\"]\n")
+    }
+
+    fn write_line(
+        board: &mut Board,
+        comments: &[String],
+        moves: &[PgnMove],
+        words: &mut Vec<String>,
+    ) {
+        Self::write_comments(comments, words);
+
+        // Black moves need their number whenever something interrupts the movetext before them
+        let mut needs_number = true;
+
+        for r#move in moves {
+            let fullmove = board.get_state().fullmove;
+
+            let san = board.move_to_san(r#move.r#move);
+
+            // Kept as a single word so that a line never ends on a move number
+            words.push(match board.turn {
+                PieceColor::White => format!("{fullmove}. {san}"),
+                PieceColor::Black if needs_number => format!("{fullmove}... {san}"),
+                PieceColor::Black => san,
+            });
+            words.extend(r#move.nags.iter().map(|nag| format!("${nag}")));
+            Self::write_comments(&r#move.comments, words);
+
+            for variation in &r#move.variations {
+                let mut variation_board = board.clone();
+
+                words.push("(".into());
+                Self::write_line(
+                    &mut variation_board,
+                    &variation.comments,
+                    &variation.moves,
+                    words,
+                );
+                words.push(")".into());
+            }
+
+            needs_number = !r#move.comments.is_empty() || !r#move.variations.is_empty();
+            board.make_move(r#move.r#move);
+        }
+    }
+
+    // Comments are split into words so that long ones can wrap across lines
+    fn write_comments(comments: &[String], words: &mut Vec<String>) {
+        for comment in comments {
+            let comment = format!("{{{comment}}}");
+            words.extend(comment.split_whitespace().map(String::from));
+        }
+    }
+}
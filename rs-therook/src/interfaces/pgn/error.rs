@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgnError {
+    Io(String),
+    InvalidTag(String),
+    InvalidFen(FenError),
+    // Ply counts from the start of the line the move was found in
+    InvalidMove(usize, SanError),
+    UnterminatedComment,
+    UnmatchedParenthesis,
+    VariationWithoutMove,
+}
+
+impl std::fmt::Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnError::Io(error) => write!(f, "Failed to read PGN: {error}"),
+            PgnError::InvalidTag(line) => write!(f, "Invalid tag pair: {line}"),
+            PgnError::InvalidFen(error) => write!(f, "Invalid FEN tag: {error}"),
+            PgnError::InvalidMove(ply, error) => write!(f, "{error} at ply {ply}"),
+            PgnError::UnterminatedComment => write!(f, "Comment is missing its closing }}"),
+            PgnError::UnmatchedParenthesis => write!(f, "Variation parentheses do not match"),
+            PgnError::VariationWithoutMove => write!(f, "Variation does not follow a move"),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
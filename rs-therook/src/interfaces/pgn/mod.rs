@@ -0,0 +1,253 @@
+mod _parse;
+mod _write;
+mod error;
+mod reader;
+mod result;
+
+use crate::engine::*;
+use crate::interfaces::*;
+pub use error::*;
+pub use reader::*;
+pub use result::*;
+
+// https://www.saremba.de/chessgml/standards/pgn/pgn-complete.htm
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgnGame {
+    // In the order they were read, the seven tag roster is always written out first
+    pub tags: Vec<(String, String)>,
+    pub comments: Vec<String>,
+    pub moves: Vec<PgnMove>,
+    pub result: PgnResult,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PgnMove {
+    pub r#move: Move,
+    // Numeric annotation glyphs, with suffixes like ! and ?! converted to their $1 to $6 equivalents
+    pub nags: Vec<u8>,
+    pub comments: Vec<String>,
+    // Alternatives to this move, each starting from the position before it
+    pub variations: Vec<PgnVariation>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgnVariation {
+    pub comments: Vec<String>,
+    pub moves: Vec<PgnMove>,
+}
+
+impl PgnGame {
+    pub const SEVEN_TAG_ROSTER: [&str; 7] =
+        ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, existing)) => *existing = value.into(),
+            None => self.tags.push((name.into(), value.into())),
+        }
+    }
+
+    // Games may start from a custom position given by the SetUp and FEN tags, which has to be one
+    // the moves can be replayed from
+    pub fn get_initial_board(&self) -> Result<Board, FenError> {
+        match self.get_tag("FEN") {
+            Some(fen) => Board::try_from_fen_strict(fen),
+            None => Ok(Board::initial()),
+        }
+    }
+
+    // Position at the end of the main line
+    pub fn get_board(&self) -> Result<Board, FenError> {
+        let mut board = self.get_initial_board()?;
+
+        for r#move in &self.moves {
+            board.make_move(r#move.r#move);
+        }
+
+        Ok(board)
+    }
+}
+
+impl PgnMove {
+    pub fn new(r#move: Move) -> Self {
+        PgnMove {
+            r#move,
+            nags: vec![],
+            comments: vec![],
+            variations: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::square;
+
+    const OPERA: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+[ECO "C41"]
+
+1.e4 e5 2.Nf3 d6 3.d4 Bg4 {This is a weak move already.} 4.dxe5 Bxf3 5.Qxf3 dxe5
+6.Bc4 Nf6 7.Qb3 Qe7 8.Nc3 c6 9.Bg5 b5?! 10.Nxb5! cxb5 11.Bxb5+ Nbd7 12.O-O-O Rd8
+13.Rxd7 Rxd7 14.Rd1 Qe6 15.Bxd7+ Nxd7 (15...Kd8 16.Bxe6+) 16.Qb8+ $18 Nxb8 17.Rd8# 1-0
+"#;
+
+    const EXPORTED: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+[ECO "C41"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4 {This is a weak move already.} 4. dxe5 Bxf3
+5. Qxf3 dxe5 6. Bc4 Nf6 7. Qb3 Qe7 8. Nc3 c6 9. Bg5 b5 $6 10. Nxb5 $1 cxb5
+11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7 14. Rd1 Qe6 15. Bxd7+ Nxd7
+(15... Kd8 16. Bxe6+) 16. Qb8+ $18 Nxb8 17. Rd8# 1-0
+
+"#;
+
+    #[test]
+    fn parse() {
+        let game = PgnGame::parse(OPERA).unwrap();
+
+        assert_eq!(game.tags.len(), 8);
+        assert_eq!(game.get_tag("White"), Some("Paul Morphy"));
+        assert_eq!(game.get_tag("ECO"), Some("C41"));
+        assert_eq!(game.result, PgnResult::WhiteWins);
+        assert_eq!(game.moves.len(), 33);
+
+        assert_eq!(game.moves[5].comments, vec!["This is a weak move already."]);
+        assert_eq!(game.moves[17].nags, vec![6]);
+        assert_eq!(game.moves[18].nags, vec![1]);
+        assert_eq!(game.moves[30].nags, vec![18]);
+
+        let variation = &game.moves[29].variations[0];
+        assert_eq!(
+            variation.moves[0].r#move,
+            Move::new(square!(E8), square!(D8), MoveFlag::None)
+        );
+        assert_eq!(variation.moves.len(), 2);
+
+        let board = game.get_board().unwrap();
+        assert_eq!(
+            &board,
+            "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17"
+        );
+        assert_eq!(PgnResult::from(board.outcome().unwrap()), game.result);
+    }
+
+    #[test]
+    fn write() {
+        let game = PgnGame::parse(OPERA).unwrap();
+        let pgn = game.to_pgn().unwrap();
+
+        assert_eq!(pgn, EXPORTED);
+        assert!(pgn.lines().all(|line| line.len() <= PgnGame::LINE_WIDTH));
+        assert_eq!(PgnGame::parse(&pgn).unwrap(), game);
+    }
+
+    #[test]
+    fn write_defaults() {
+        let mut game = PgnGame::default();
+        game.set_tag("Annotator", "A \"quoted\" name");
+        game.set_tag("FEN", "4k3/8/8/8/8/8/8/R3K3 b - - 0 40");
+        game.set_tag("SetUp", "1");
+
+        let r#move = Move::new(square!(E8), square!(D8), MoveFlag::None);
+        game.moves.push(PgnMove::new(r#move));
+
+        assert_eq!(
+            game.to_pgn().unwrap(),
+            "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n[White \"?\"]\n\
+             [Black \"?\"]\n[Result \"*\"]\n[Annotator \"A \\\"quoted\\\" name\"]\n\
+             [FEN \"4k3/8/8/8/8/8/8/R3K3 b - - 0 40\"]\n[SetUp \"1\"]\n\n40... Kd8 *\n\n"
+        );
+    }
+
+    #[test]
+    fn nested_variations_and_comments() {
+        let game = PgnGame::parse(
+            "{Opening} 1. e4 (1. d4 d5 (1... Nf6 2. c4) 2. c4) ; the best\n1... c5 *",
+        )
+        .unwrap();
+
+        assert_eq!(game.comments, vec!["Opening"]);
+        assert_eq!(game.moves.len(), 2);
+        assert_eq!(game.moves[0].comments, vec!["the best"]);
+        assert_eq!(game.result, PgnResult::Unknown);
+
+        let variation = &game.moves[0].variations[0];
+        assert_eq!(variation.moves.len(), 3);
+        assert_eq!(variation.moves[1].variations[0].moves.len(), 2);
+
+        assert_eq!(
+            game.to_pgn().unwrap().lines().nth(8),
+            Some("{Opening} 1. e4 {the best} (1. d4 d5 (1... Nf6 2. c4) 2. c4) 1... c5 *")
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |pgn| PgnGame::parse(pgn).err();
+
+        assert_eq!(
+            parse("1. e4 e5 2. Ke3"),
+            Some(PgnError::InvalidMove(3, SanError::Illegal("Ke3".into())))
+        );
+        assert_eq!(parse("1. e4 {open"), Some(PgnError::UnterminatedComment));
+        assert_eq!(parse("1. e4 (1. d4"), Some(PgnError::UnmatchedParenthesis));
+        assert_eq!(parse("1. e4 )"), Some(PgnError::UnmatchedParenthesis));
+        assert_eq!(parse("(1. d4) 1. e4"), Some(PgnError::VariationWithoutMove));
+        assert_eq!(
+            parse("[Event Paris]\n\n1. e4"),
+            Some(PgnError::InvalidTag("[Event Paris]".into()))
+        );
+        assert!(matches!(
+            parse("[FEN \"8/8 w\"]\n\n1. e4"),
+            Some(PgnError::InvalidFen(_))
+        ));
+        assert!(matches!(
+            parse("[FEN \"8/8/8/8/8/8/8/4K3 b - - 0 1\"]\n[SetUp \"1\"]\n\n1... Kd2 *"),
+            Some(PgnError::InvalidFen(_))
+        ));
+    }
+
+    #[test]
+    fn reader() {
+        let input = format!(
+            "{OPERA}\n[Event \"Broken\"]\n\n1. e4 e4 *\n\n% escaped line\n[Event \"Short\"]\n\
+             [Result \"0-1\"]\n\n1. f3 e5 2. g4 {{fool's\n[mate]}} Qh4#\n"
+        );
+
+        let games = PgnReader::new(input.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(games.len(), 3);
+
+        assert_eq!(games[0].as_ref().unwrap().moves.len(), 33);
+        assert!(matches!(games[1], Err(PgnError::InvalidMove(2, _))));
+
+        let short = games[2].as_ref().unwrap();
+        assert_eq!(short.get_tag("Event"), Some("Short"));
+        assert_eq!(short.result, PgnResult::BlackWins);
+        assert_eq!(short.moves[2].comments, vec!["fool's [mate]"]);
+        assert_eq!(
+            short.get_board().unwrap().outcome(),
+            Some(Outcome::Checkmate(PieceColor::Black))
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+use std::io::{BufRead, Lines};
+
+// Streams games one at a time so that large databases never have to be held in memory at once.
+// A game that fails to parse is reported without stopping the games after it
+pub struct PgnReader<R: BufRead> {
+    lines: Lines<R>,
+    // First tag line of the next game, read while looking for the end of the previous one
+    pending: Option<String>,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(input: R) -> Self {
+        PgnReader {
+            lines: input.lines(),
+            pending: None,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut text = String::new();
+        let mut has_movetext = false;
+        let mut in_comment = false;
+
+        loop {
+            let line = match self.pending.take().map(Ok).or_else(|| self.lines.next()) {
+                Some(Ok(line)) => line,
+                Some(Err(error)) => return Some(Err(PgnError::Io(error.to_string()))),
+                None => break,
+            };
+
+            let trimmed = line.trim();
+
+            // A tag after the movetext can only belong to the next game
+            if !in_comment && has_movetext && trimmed.starts_with('[') {
+                self.pending = Some(line);
+                break;
+            }
+
+            if !in_comment && !trimmed.is_empty() && !trimmed.starts_with(['[', '%']) {
+                has_movetext = true;
+            }
+
+            if !line.starts_with('%') {
+                for char in line.chars() {
+                    match char {
+                        '{' => in_comment = true,
+                        '}' => in_comment = false,
+                        ';' if !in_comment => break,
+                        _ => {}
+                    }
+                }
+            }
+
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        if text.trim().is_empty() {
+            return None;
+        }
+
+        Some(PgnGame::parse(&text))
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PgnResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    #[default]
+    Unknown,
+}
+
+impl PgnResult {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "1-0" => Some(PgnResult::WhiteWins),
+            "0-1" => Some(PgnResult::BlackWins),
+            "1/2-1/2" => Some(PgnResult::Draw),
+            "*" => Some(PgnResult::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PgnResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnResult::WhiteWins => write!(f, "1-0"),
+            PgnResult::BlackWins => write!(f, "0-1"),
+            PgnResult::Draw => write!(f, "1/2-1/2"),
+            PgnResult::Unknown => write!(f, "*"),
+        }
+    }
+}
+
+impl From<Outcome> for PgnResult {
+    fn from(outcome: Outcome) -> Self {
+        match outcome.get_winner() {
+            Some(PieceColor::White) => PgnResult::WhiteWins,
+            Some(PieceColor::Black) => PgnResult::BlackWins,
+            None => PgnResult::Draw,
+        }
+    }
+}
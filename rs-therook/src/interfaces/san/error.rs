@@ -0,0 +1,21 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SanError {
+    // Not shaped like a move at all
+    Invalid(String),
+    // Well formed, but no legal move matches
+    Illegal(String),
+    // Well formed, but more than one legal move matches
+    Ambiguous(String),
+}
+
+impl std::fmt::Display for SanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SanError::Invalid(san) => write!(f, "Invalid SAN: {san}"),
+            SanError::Illegal(san) => write!(f, "Illegal move: {san}"),
+            SanError::Ambiguous(san) => write!(f, "Ambiguous move: {san}"),
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
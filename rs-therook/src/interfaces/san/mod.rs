@@ -0,0 +1,392 @@
+mod error;
+
+use crate::engine::*;
+pub use error::*;
+
+// https://www.chessprogramming.org/Algebraic_Chess_Notation#Standard_Algebraic_Notation_.28SAN.29
+
+impl Board {
+    pub fn move_to_san(&self, r#move: Move) -> String {
+        let start = r#move.get_start();
+        let end = r#move.get_end();
+        let piece = self.squares[start as usize].unwrap();
+        let r#type = piece.get_type();
+
+        let mut san = String::new();
+
+        if r#move.get_flag() == MoveFlag::Castle {
+            san.push_str(if end > start { "O-O" } else { "O-O-O" });
+        } else {
+            let is_capture =
+                r#move.get_flag() == MoveFlag::EnPassant || self.squares[end as usize].is_some();
+
+            if r#type == PieceType::Pawn {
+                if is_capture {
+                    san.push(Self::get_file_char(start));
+                }
+            } else {
+                san.push(Self::get_piece_char(r#type));
+                san.push_str(&self.get_disambiguation(r#move));
+            }
+
+            if is_capture {
+                san.push('x');
+            }
+
+            san.push_str(&Move::format_square(end));
+
+            if let Some(r#type) = r#move.get_promote_piece_type() {
+                san.push('=');
+                san.push(Self::get_piece_char(r#type));
+            }
+        }
+
+        let mut board = self.clone();
+        board.make_move(r#move);
+
+        if board.is_in_check() {
+            san.push(if board.calculate_moves().is_empty() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+
+        san
+    }
+
+    // Accepts the strict form along with common variations: 0-0 for castling, missing or extra check
+    // and annotation suffixes, and promotions without the =
+    pub fn parse_san(&self, san: &str) -> Result<Move, SanError> {
+        let invalid = || SanError::Invalid(san.into());
+
+        let text = san
+            .trim()
+            .trim_end_matches(['+', '#', '!', '?'])
+            .replace('0', "O");
+        let moves = self.calculate_moves();
+
+        if text == "O-O" || text == "O-O-O" {
+            let kingside = text == "O-O";
+
+            return moves
+                .into_iter()
+                .find(|r#move| {
+                    r#move.get_flag() == MoveFlag::Castle
+                        && (r#move.get_end() > r#move.get_start()) == kingside
+                })
+                .ok_or_else(|| SanError::Illegal(san.into()));
+        }
+
+        let mut chars = text
+            .chars()
+            .filter(|&char| char != 'x' && char != '-')
+            .collect::<Vec<_>>();
+
+        // Promotion piece at the end, after either an = or the rank
+        let mut promotion = None;
+        if let [.., before, last] = chars[..] {
+            if (before == '=' || before.is_ascii_digit()) && "QRBNqrbn".contains(last) {
+                promotion = Some(Self::parse_piece_char(last.to_ascii_uppercase()).unwrap());
+                chars.pop();
+
+                if before == '=' {
+                    chars.pop();
+                }
+            }
+        }
+
+        let r#type = match chars.first() {
+            Some(&char) if char.is_ascii_uppercase() => {
+                chars.remove(0);
+                Self::parse_piece_char(char).ok_or_else(invalid)?
+            }
+            _ => PieceType::Pawn,
+        };
+
+        if chars.len() < 2 || chars.len() > 4 {
+            return Err(invalid());
+        }
+
+        let destination = chars
+            .split_off(chars.len() - 2)
+            .into_iter()
+            .collect::<String>();
+        let end = Move::parse_square(&destination).ok_or_else(invalid)?;
+
+        let mut file = None;
+        let mut rank = None;
+
+        for char in chars {
+            match char {
+                'a'..='h' if file.is_none() && rank.is_none() => file = Some(char as u8 - b'a'),
+                '1'..='8' if rank.is_none() => rank = Some(char as u8 - b'1'),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut candidates = moves.into_iter().filter(|r#move| {
+            let start = r#move.get_start();
+
+            r#move.get_end() == end
+                && r#move.get_flag() != MoveFlag::Castle
+                && r#move.get_promote_piece_type() == promotion
+                && self.squares[start as usize].map(|piece| piece.get_type()) == Some(r#type)
+                && file.is_none_or(|file| start & 7 == file)
+                && rank.is_none_or(|rank| start >> 3 == rank)
+        });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(r#move), None) => Ok(r#move),
+            (Some(_), Some(_)) => Err(SanError::Ambiguous(san.into())),
+            (None, _) => Err(SanError::Illegal(san.into())),
+        }
+    }
+
+    // Only as much of the start square as it takes to tell identical pieces on the same target apart
+    fn get_disambiguation(&self, r#move: Move) -> String {
+        let start = r#move.get_start();
+        let piece = self.squares[start as usize];
+
+        let others = self
+            .calculate_moves()
+            .into_iter()
+            .filter(|other| {
+                other.get_end() == r#move.get_end()
+                    && other.get_start() != start
+                    && self.squares[other.get_start() as usize] == piece
+            })
+            .map(|other| other.get_start())
+            .collect::<Vec<_>>();
+
+        if others.is_empty() {
+            String::new()
+        } else if others.iter().all(|other| other & 7 != start & 7) {
+            Self::get_file_char(start).into()
+        } else if others.iter().all(|other| other >> 3 != start >> 3) {
+            Self::get_rank_char(start).into()
+        } else {
+            Move::format_square(start)
+        }
+    }
+
+    fn get_file_char(square: u8) -> char {
+        (b'a' + (square & 7)) as char
+    }
+
+    fn get_rank_char(square: u8) -> char {
+        (b'1' + (square >> 3)) as char
+    }
+
+    fn get_piece_char(r#type: PieceType) -> char {
+        match r#type {
+            PieceType::King => 'K',
+            PieceType::Queen => 'Q',
+            PieceType::Rook => 'R',
+            PieceType::Bishop => 'B',
+            PieceType::Knight => 'N',
+            PieceType::Pawn => 'P',
+        }
+    }
+
+    fn parse_piece_char(char: char) -> Option<PieceType> {
+        match char {
+            'K' => Some(PieceType::King),
+            'Q' => Some(PieceType::Queen),
+            'R' => Some(PieceType::Rook),
+            'B' => Some(PieceType::Bishop),
+            'N' => Some(PieceType::Knight),
+            'P' => Some(PieceType::Pawn),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::square;
+
+    fn san(fen: &str, start: u8, end: u8, flag: MoveFlag) -> String {
+        Board::from_fen(fen).move_to_san(Move::new(start, end, flag))
+    }
+
+    mod move_to_san {
+        use super::*;
+
+        #[test]
+        fn pieces_and_pawns() {
+            let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+            assert_eq!(san(fen, square!(E2), square!(E4), MoveFlag::PawnDash), "e4");
+            assert_eq!(san(fen, square!(G1), square!(F3), MoveFlag::None), "Nf3");
+        }
+
+        #[test]
+        fn captures() {
+            let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 0 1";
+            assert_eq!(san(fen, square!(E4), square!(D5), MoveFlag::None), "exd5");
+
+            let fen = "4k3/8/8/3p4/8/4N3/8/4K3 w - - 0 1";
+            assert_eq!(san(fen, square!(E3), square!(D5), MoveFlag::None), "Nxd5");
+
+            let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+            assert_eq!(
+                san(fen, square!(E5), square!(D6), MoveFlag::EnPassant),
+                "exd6"
+            );
+        }
+
+        #[test]
+        fn disambiguation() {
+            let fen = "7k/8/8/8/8/8/8/R4R1K w - - 0 1";
+            assert_eq!(san(fen, square!(A1), square!(D1), MoveFlag::None), "Rad1");
+            assert_eq!(san(fen, square!(F1), square!(D1), MoveFlag::None), "Rfd1");
+
+            let fen = "7k/8/8/R7/8/8/8/R6K w - - 0 1";
+            assert_eq!(san(fen, square!(A1), square!(A3), MoveFlag::None), "R1a3");
+            assert_eq!(san(fen, square!(A5), square!(A3), MoveFlag::None), "R5a3");
+
+            let fen = "8/8/6k1/8/8/Q7/8/Q1Q4K w - - 0 1";
+            assert_eq!(san(fen, square!(A1), square!(B2), MoveFlag::None), "Qa1b2");
+            assert_eq!(san(fen, square!(A3), square!(B2), MoveFlag::None), "Q3b2");
+            assert_eq!(san(fen, square!(C1), square!(B2), MoveFlag::None), "Qcb2");
+        }
+
+        #[test]
+        fn pinned_piece_needs_no_disambiguation() {
+            let fen = "4k3/8/8/8/1b6/8/3N4/4K1N1 w - - 0 1";
+            assert_eq!(san(fen, square!(G1), square!(F3), MoveFlag::None), "Nf3");
+        }
+
+        #[test]
+        fn castling() {
+            let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+            assert_eq!(san(fen, square!(E1), square!(H1), MoveFlag::Castle), "O-O");
+            assert_eq!(
+                san(fen, square!(E1), square!(A1), MoveFlag::Castle),
+                "O-O-O"
+            );
+        }
+
+        #[test]
+        fn promotion() {
+            let fen = "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+
+            assert_eq!(
+                san(fen, square!(A7), square!(A8), MoveFlag::PromoteQueen),
+                "a8=Q"
+            );
+            assert_eq!(
+                san(fen, square!(A7), square!(B8), MoveFlag::PromoteKnight),
+                "axb8=N"
+            );
+        }
+
+        #[test]
+        fn check_and_mate() {
+            let fen = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1";
+
+            assert_eq!(san(fen, square!(A1), square!(A8), MoveFlag::None), "Ra8#");
+            assert_eq!(san(fen, square!(A1), square!(A7), MoveFlag::None), "Ra7");
+
+            let fen = "6k1/8/8/8/8/8/8/R5K1 w - - 0 1";
+            assert_eq!(san(fen, square!(A1), square!(A8), MoveFlag::None), "Ra8+");
+        }
+    }
+
+    mod parse_san {
+        use super::*;
+
+        fn parse(fen: &str, san: &str) -> Result<Move, SanError> {
+            Board::from_fen(fen).parse_san(san)
+        }
+
+        #[test]
+        fn variants() {
+            let fen = "4k2r/P7/8/8/8/8/8/R3K2R w KQk - 0 1";
+            let castle = Ok(Move::new(square!(E1), square!(H1), MoveFlag::Castle));
+            let promote = Ok(Move::new(square!(A7), square!(A8), MoveFlag::PromoteQueen));
+
+            assert_eq!(parse(fen, "O-O"), castle);
+            assert_eq!(parse(fen, "0-0"), castle);
+            assert_eq!(parse(fen, "O-O+"), castle);
+            assert_eq!(
+                parse(fen, "0-0-0"),
+                Ok(Move::new(square!(E1), square!(A1), MoveFlag::Castle))
+            );
+
+            assert_eq!(
+                parse(fen, "axb8=Q"),
+                Err(SanError::Illegal("axb8=Q".into()))
+            );
+            assert_eq!(parse(fen, "a8=Q"), promote);
+            assert_eq!(parse(fen, "a8Q"), promote);
+            assert_eq!(parse(fen, "a8=q"), promote);
+            assert_eq!(parse(fen, "xa8=Q"), promote);
+            assert_eq!(
+                parse(fen, "a8=N"),
+                Ok(Move::new(square!(A7), square!(A8), MoveFlag::PromoteKnight))
+            );
+        }
+
+        #[test]
+        fn disambiguation() {
+            let fen = "8/8/6k1/8/8/Q7/8/Q1Q4K w - - 0 1";
+
+            assert_eq!(parse(fen, "Qb2"), Err(SanError::Ambiguous("Qb2".into())));
+            assert_eq!(parse(fen, "Qab2"), Err(SanError::Ambiguous("Qab2".into())));
+            assert_eq!(
+                parse(fen, "Qa1b2"),
+                Ok(Move::new(square!(A1), square!(B2), MoveFlag::None))
+            );
+            assert_eq!(
+                parse(fen, "Q3b2"),
+                Ok(Move::new(square!(A3), square!(B2), MoveFlag::None))
+            );
+            assert_eq!(
+                parse(fen, "Qcb2"),
+                Ok(Move::new(square!(C1), square!(B2), MoveFlag::None))
+            );
+        }
+
+        #[test]
+        fn errors() {
+            let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+            assert_eq!(parse(fen, ""), Err(SanError::Invalid("".into())));
+            assert_eq!(parse(fen, "Zf3"), Err(SanError::Invalid("Zf3".into())));
+            assert_eq!(parse(fen, "Nf9"), Err(SanError::Invalid("Nf9".into())));
+            assert_eq!(
+                parse(fen, "Nb1c3d4"),
+                Err(SanError::Invalid("Nb1c3d4".into()))
+            );
+            assert_eq!(
+                parse(fen, "Nb1c3"),
+                Ok(Move::new(square!(B1), square!(C3), MoveFlag::None))
+            );
+            assert_eq!(parse(fen, "Nf4"), Err(SanError::Illegal("Nf4".into())));
+            assert_eq!(parse(fen, "e5"), Err(SanError::Illegal("e5".into())));
+            assert_eq!(parse(fen, "O-O"), Err(SanError::Illegal("O-O".into())));
+        }
+    }
+
+    // Morphy vs Duke Karl / Count Isouard, Paris 1858
+    #[test]
+    fn game_round_trip() {
+        let mut board = Board::initial();
+
+        let game = "e4 e5 Nf3 d6 d4 Bg4 dxe5 Bxf3 Qxf3 dxe5 Bc4 Nf6 Qb3 Qe7 Nc3 c6 Bg5 b5 Nxb5 cxb5 \
+                    Bxb5+ Nbd7 O-O-O Rd8 Rxd7 Rxd7 Rd1 Qe6 Bxd7+ Nxd7 Qb8+ Nxb8 Rd8#";
+
+        for san in game.split_whitespace() {
+            let r#move = board.parse_san(san).unwrap();
+            assert_eq!(board.move_to_san(r#move), san);
+
+            board.make_move(r#move);
+        }
+
+        assert_eq!(board.outcome(), Some(Outcome::Checkmate(PieceColor::White)));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::engine::*;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
 
@@ -8,14 +9,24 @@ pub struct Stockfish {
 }
 
 impl Stockfish {
+    // Used when the STOCKFISH environment variable does not point to another binary
+    pub const DEFAULT_PATH: &str = "../stockfish/stockfish";
+
     pub fn new() -> Self {
-        let mut process = Command::new("../stockfish/stockfish")
+        Self::try_new().expect("Failed to spawn stockfish")
+    }
+
+    // None when there is no Stockfish binary to spawn on this machine
+    pub fn try_new() -> Option<Self> {
+        let path = std::env::var("STOCKFISH").unwrap_or_else(|_| Self::DEFAULT_PATH.into());
+
+        let mut process = Command::new(path)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .spawn()
-            .expect("Failed to spawn stockfish");
+            .ok()?;
         let stdin = process.stdin.take().expect("Failed to open stdin");
-        let stdout = BufReader::new(process.stdout.take().expect("Failed to open stdin"));
+        let stdout = BufReader::new(process.stdout.take().expect("Failed to open stdout"));
 
         let mut stockfish = Stockfish {
             process,
@@ -26,7 +37,7 @@ impl Stockfish {
         stockfish.write("uci".into());
         stockfish.read_until("uciok".into());
 
-        stockfish
+        Some(stockfish)
     }
 
     pub fn write(&mut self, text: String) {
@@ -47,6 +58,33 @@ impl Stockfish {
 
         lines
     }
+
+    // Node counts per root move from go perft, with moves in UCI notation
+    pub fn go_perft(&mut self, fen: &str, chess960: bool, depth: u8) -> Vec<(String, u64)> {
+        self.write(format!("setoption name UCI_Chess960 value {chess960}"));
+        self.write(format!("position fen {fen}"));
+        self.write(format!("go perft {depth}"));
+
+        let perft_regex = regex::Regex::new(r"^(\w\d\w\d\w?): (\d+)$").unwrap();
+
+        self.read_until("Nodes searched:".into())
+            .iter()
+            .filter_map(|line| perft_regex.captures(line))
+            .map(|captures| (captures[1].to_owned(), captures[2].parse().unwrap()))
+            .collect()
+    }
+}
+
+impl PerftOracle for Stockfish {
+    fn divide(&mut self, board: &Board, depth: u8) -> Vec<(String, u64)> {
+        self.go_perft(&board.to_fen(), board.chess960, depth)
+    }
+}
+
+impl Default for Stockfish {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Drop for Stockfish {
@@ -54,3 +92,39 @@ impl Drop for Stockfish {
         self.process.kill().expect("Failed to kill stockfish");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs deeper than the embedded counts test, since Stockfish also points at the position at fault
+    const MAX_NODES: u64 = 5_000_000;
+
+    #[test]
+    fn perft() {
+        // Without a Stockfish binary the embedded counts are all there is to compare against
+        let Some(mut stockfish) = Stockfish::try_new() else {
+            eprintln!("Stockfish not found, falling back to the embedded perft counts");
+
+            for position in PERFT_POSITIONS {
+                let mut board = Board::from_fen(position.fen);
+
+                for (depth, expected) in position.get_depths(MAX_NODES) {
+                    assert_eq!(board.perft(depth), expected, "{}", position.name);
+                }
+            }
+
+            return;
+        };
+
+        for position in PERFT_POSITIONS {
+            let board = Board::from_fen(position.fen);
+
+            for (depth, _) in position.get_depths(MAX_NODES) {
+                if let Some(bug) = board.bisect(&mut stockfish, depth) {
+                    panic!("{} depth {depth}\n{bug}", position.name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,476 @@
+use crate::engine::*;
+use itertools::Itertools;
+use std::io::BufRead;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// https://backscattering.de/chess/uci/
+
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct GoCommand {
+    pub depth: Option<u8>,
+    pub nodes: Option<u64>,
+    pub movetime: Option<Duration>,
+    pub wtime: Option<Duration>,
+    pub btime: Option<Duration>,
+    pub winc: Option<Duration>,
+    pub binc: Option<Duration>,
+    pub movestogo: Option<u32>,
+    pub infinite: bool,
+}
+
+impl GoCommand {
+    pub fn parse(arguments: &str) -> Self {
+        let mut go = GoCommand::default();
+        let mut tokens = arguments.split_whitespace();
+
+        while let Some(token) = tokens.next() {
+            let millis = |value: Option<&str>| {
+                value
+                    .and_then(|v| v.parse::<i64>().ok())
+                    .map(|ms| Duration::from_millis(ms.max(0) as u64))
+            };
+
+            match token {
+                "depth" => go.depth = tokens.next().and_then(|v| v.parse().ok()),
+                "nodes" => go.nodes = tokens.next().and_then(|v| v.parse().ok()),
+                "movetime" => go.movetime = millis(tokens.next()),
+                "wtime" => go.wtime = millis(tokens.next()),
+                "btime" => go.btime = millis(tokens.next()),
+                "winc" => go.winc = millis(tokens.next()),
+                "binc" => go.binc = millis(tokens.next()),
+                "movestogo" => go.movestogo = tokens.next().and_then(|v| v.parse().ok()),
+                "infinite" => go.infinite = true,
+                _ => {}
+            }
+        }
+
+        go
+    }
+
+    // Spend an even share of the remaining clock on every move left until the next time control,
+    // plus most of the increment, while always leaving the move overhead on the clock
+    pub fn time_budget(&self, color: PieceColor, overhead: Duration) -> Option<Duration> {
+        if self.infinite {
+            return None;
+        }
+
+        if let Some(movetime) = self.movetime {
+            return Some(movetime.saturating_sub(overhead));
+        }
+
+        let (time, increment) = match color {
+            PieceColor::White => (self.wtime?, self.winc.unwrap_or_default()),
+            PieceColor::Black => (self.btime?, self.binc.unwrap_or_default()),
+        };
+
+        let moves_to_go = self.movestogo.unwrap_or(30).max(1);
+        let budget = time / moves_to_go + increment * 3 / 4;
+
+        Some(
+            budget
+                .saturating_sub(overhead)
+                .min(time.saturating_sub(overhead)),
+        )
+    }
+
+    pub fn get_limits(&self, color: PieceColor, overhead: Duration) -> SearchLimits {
+        SearchLimits {
+            depth: self.depth,
+            nodes: self.nodes,
+            time: self.time_budget(color, overhead),
+        }
+    }
+}
+
+pub struct Uci {
+    board: Board,
+
+    move_overhead: Duration,
+    table: Arc<TranspositionTable>,
+    chess960: bool,
+
+    stop: Arc<AtomicBool>,
+    search: Option<JoinHandle<()>>,
+}
+
+impl Uci {
+    pub const NAME: &str = "therook";
+    pub const AUTHOR: &str = "zS1L3NT";
+    pub const MAX_HASH_MB: usize = 4096;
+
+    pub fn new() -> Self {
+        Uci {
+            board: Board::initial(),
+
+            move_overhead: Duration::from_millis(10),
+            table: Arc::new(TranspositionTable::default()),
+            chess960: false,
+
+            stop: Arc::new(AtomicBool::new(false)),
+            search: None,
+        }
+    }
+
+    pub fn run(&mut self, input: impl BufRead) {
+        for line in input.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+
+            if !self.handle(&line) {
+                return;
+            }
+        }
+
+        self.stop_search();
+    }
+
+    // Returns false once the GUI asks us to quit
+    pub fn handle(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        let (command, arguments) = line.split_once(' ').unwrap_or((line, ""));
+
+        match command {
+            "uci" => {
+                println!("id name {}", Self::NAME);
+                println!("id author {}", Self::AUTHOR);
+                println!(
+                    "option name Hash type spin default {} min 1 max {}",
+                    TranspositionTable::DEFAULT_MB,
+                    Self::MAX_HASH_MB
+                );
+                println!("option name Move Overhead type spin default 10 min 0 max 5000");
+                println!("option name UCI_Chess960 type check default false");
+                println!("uciok");
+            }
+            "isready" => println!("readyok"),
+            "ucinewgame" => {
+                self.stop_search();
+                self.board = Board::initial();
+                self.table.clear();
+            }
+            "position" => self.position(arguments),
+            "go" => self.go(GoCommand::parse(arguments)),
+            "stop" => self.stop_search(),
+            "setoption" => self.set_option(arguments),
+            "d" => println!("{:?}", self.board),
+            "eval" => println!("{:?}", HandCrafted.breakdown(&self.board)),
+            "quit" => {
+                self.stop_search();
+                return false;
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    fn position(&mut self, arguments: &str) {
+        let (position, moves) = match arguments.split_once("moves") {
+            Some((position, moves)) => (position.trim(), moves.trim()),
+            None => (arguments.trim(), ""),
+        };
+
+        self.board = if let Some(fen) = position.strip_prefix("fen") {
+            match Board::try_from_fen_strict(fen.trim()) {
+                Ok(board) => board,
+                Err(error) => {
+                    println!("info string {error}");
+                    return;
+                }
+            }
+        } else {
+            Board::initial()
+        };
+
+        // The GUI decides how castling moves are written, whatever the position looks like
+        self.board.chess960 = self.chess960;
+
+        for text in moves.split_whitespace() {
+            match self.board.parse_uci_move(text) {
+                Ok(r#move) => self.board.make_move(r#move),
+                Err(error) => {
+                    println!("info string {error}, ignoring remaining moves");
+                    break;
+                }
+            }
+        }
+    }
+
+    fn go(&mut self, go: GoCommand) {
+        self.stop_search();
+        self.stop.store(false, Ordering::Relaxed);
+
+        let board = self.board.clone();
+        let limits = go.get_limits(board.turn, self.move_overhead);
+        let table = self.table.clone();
+        let stop = self.stop.clone();
+        let chess960 = board.chess960;
+
+        self.search = Some(thread::spawn(move || {
+            let mut search = Search::new(board, limits, table.clone(), stop.clone());
+            let result = search.run(|result| {
+                println!(
+                    "info depth {} score {:?} nodes {} nps {} hashfull {} time {} pv {}",
+                    result.depth,
+                    result.score,
+                    result.nodes,
+                    result.get_nps(),
+                    table.hashfull(),
+                    result.time.as_millis(),
+                    result
+                        .pv
+                        .iter()
+                        .map(|r#move| r#move.to_uci(chess960))
+                        .join(" ")
+                );
+            });
+
+            // The GUI expects no bestmove for an infinite search until it sends stop
+            while go.infinite && !stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            match result.best_move {
+                Some(r#move) => println!("bestmove {}", r#move.to_uci(chess960)),
+                None => println!("bestmove 0000"),
+            }
+        }));
+    }
+
+    fn stop_search(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+
+        if let Some(search) = self.search.take() {
+            search.join().expect("Search thread panicked");
+        }
+    }
+
+    fn set_option(&mut self, arguments: &str) {
+        let Some(arguments) = arguments.trim().strip_prefix("name ") else {
+            return;
+        };
+
+        let (name, value) = match arguments.split_once(" value ") {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => (arguments.trim(), ""),
+        };
+
+        match name.to_lowercase().as_str() {
+            "hash" => match value.parse::<usize>() {
+                Ok(mb) => {
+                    self.stop_search();
+
+                    // The search thread has let go of the old table, so dropping it here frees its
+                    // memory before the new one needs it
+                    let mb = mb.clamp(1, Self::MAX_HASH_MB);
+                    self.table = Arc::new(TranspositionTable::new(1));
+
+                    match TranspositionTable::try_new(mb) {
+                        Ok(table) => self.table = Arc::new(table),
+                        Err(error) => {
+                            println!("info string Could not allocate {mb} MB, using 1 MB: {error}")
+                        }
+                    }
+                }
+                Err(_) => println!("info string Invalid value for {name}: {value}"),
+            },
+            "move overhead" => match value.parse::<u64>() {
+                Ok(ms) => self.move_overhead = Duration::from_millis(ms.min(5000)),
+                Err(_) => println!("info string Invalid value for {name}: {value}"),
+            },
+            "uci_chess960" => match value.parse::<bool>() {
+                Ok(chess960) => {
+                    self.chess960 = chess960;
+                    self.board.chess960 = chess960;
+                }
+                Err(_) => println!("info string Invalid value for {name}: {value}"),
+            },
+            _ => println!("info string Unknown option {name}"),
+        }
+    }
+}
+
+impl Default for Uci {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Uci {
+    fn drop(&mut self) {
+        self.stop_search();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod position {
+        use super::*;
+
+        #[test]
+        fn startpos() {
+            let mut uci = Uci::new();
+
+            uci.handle("position startpos");
+            assert_eq!(
+                &uci.board,
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+            );
+        }
+
+        #[test]
+        fn startpos_with_moves() {
+            let mut uci = Uci::new();
+
+            uci.handle("position startpos moves e2e4 e7e5 g1f3");
+            assert_eq!(
+                &uci.board,
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+            );
+        }
+
+        #[test]
+        fn fen_with_moves() {
+            let mut uci = Uci::new();
+
+            uci.handle("position fen r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1 moves e1g1 e8c8");
+            assert_eq!(&uci.board, "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+        }
+
+        #[test]
+        fn chess960() {
+            let mut uci = Uci::new();
+            let fen = "1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1";
+
+            uci.handle("setoption name UCI_Chess960 value true");
+            uci.handle(&format!("position fen {fen} moves g1b1 g8h8"));
+            assert_eq!(&uci.board, "1r3rk1/8/8/8/8/8/8/2KR3R w - - 2 2");
+
+            // Without the option castling is written as the king moving to the g or c file
+            uci.handle("setoption name UCI_Chess960 value false");
+            uci.handle(&format!("position fen {fen} moves g1c1 g8g8"));
+            assert_eq!(&uci.board, "1r3rk1/8/8/8/8/8/8/2KR3R w - - 2 2");
+        }
+
+        #[test]
+        fn promotion() {
+            let mut uci = Uci::new();
+
+            uci.handle("position fen 4k3/P7/8/8/8/8/8/4K3 w - - 0 1 moves a7a8n");
+            assert_eq!(&uci.board, "N3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+        }
+
+        #[test]
+        fn invalid_fen_keeps_board() {
+            let mut uci = Uci::new();
+
+            uci.handle("position startpos moves e2e4");
+            uci.handle("position fen rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+            assert_eq!(
+                &uci.board,
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+            );
+        }
+
+        #[test]
+        fn stops_at_illegal_move() {
+            let mut uci = Uci::new();
+
+            uci.handle("position startpos moves e2e4 e2e4 e7e5");
+            assert_eq!(
+                &uci.board,
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+            );
+        }
+    }
+
+    mod go {
+        use super::*;
+
+        #[test]
+        fn parse() {
+            assert_eq!(
+                GoCommand::parse("wtime 60000 btime 50000 winc 1000 binc 500 movestogo 20"),
+                GoCommand {
+                    wtime: Some(Duration::from_millis(60000)),
+                    btime: Some(Duration::from_millis(50000)),
+                    winc: Some(Duration::from_millis(1000)),
+                    binc: Some(Duration::from_millis(500)),
+                    movestogo: Some(20),
+                    ..Default::default()
+                }
+            );
+
+            assert_eq!(
+                GoCommand::parse("depth 6 nodes 1000 movetime 250"),
+                GoCommand {
+                    depth: Some(6),
+                    nodes: Some(1000),
+                    movetime: Some(Duration::from_millis(250)),
+                    ..Default::default()
+                }
+            );
+
+            assert_eq!(
+                GoCommand::parse("infinite"),
+                GoCommand {
+                    infinite: true,
+                    ..Default::default()
+                }
+            );
+        }
+
+        #[test]
+        fn time_budget() {
+            let overhead = Duration::from_millis(10);
+
+            let go = GoCommand::parse("wtime 60000 btime 30000 winc 1000 movestogo 20");
+            assert_eq!(
+                go.time_budget(PieceColor::White, overhead),
+                Some(Duration::from_millis(3000 + 750 - 10))
+            );
+            assert_eq!(
+                go.time_budget(PieceColor::Black, overhead),
+                Some(Duration::from_millis(1500 - 10))
+            );
+
+            let go = GoCommand::parse("movetime 500");
+            assert_eq!(
+                go.time_budget(PieceColor::White, overhead),
+                Some(Duration::from_millis(490))
+            );
+
+            let go = GoCommand::parse("wtime 5 movestogo 1");
+            assert_eq!(
+                go.time_budget(PieceColor::White, overhead),
+                Some(Duration::ZERO)
+            );
+
+            assert_eq!(
+                GoCommand::parse("infinite").time_budget(PieceColor::White, overhead),
+                None
+            );
+            assert_eq!(
+                GoCommand::parse("depth 5").time_budget(PieceColor::White, overhead),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn set_option() {
+        let mut uci = Uci::new();
+
+        uci.handle("setoption name Move Overhead value 100");
+        assert_eq!(uci.move_overhead, Duration::from_millis(100));
+
+        uci.handle("setoption name Hash value 4");
+        assert_eq!(uci.table.get_size(), 4);
+    }
+}
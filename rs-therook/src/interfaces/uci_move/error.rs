@@ -0,0 +1,18 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UciMoveError {
+    // Not of the form e2e4 or e7e8q
+    Invalid(String),
+    // Well formed, but not a legal move in the position
+    Illegal(String),
+}
+
+impl std::fmt::Display for UciMoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UciMoveError::Invalid(text) => write!(f, "Invalid move: {text}"),
+            UciMoveError::Illegal(text) => write!(f, "Illegal move: {text}"),
+        }
+    }
+}
+
+impl std::error::Error for UciMoveError {}
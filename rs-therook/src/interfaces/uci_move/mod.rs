@@ -0,0 +1,96 @@
+mod error;
+
+use crate::engine::*;
+pub use error::*;
+
+// https://backscattering.de/chess/uci/#move
+impl Board {
+    // The text only names the squares and promotion, the flags come from the matching legal move.
+    // Castling is the king moving two squares, or it capturing its own rook in Chess960
+    pub fn parse_uci_move(&self, text: &str) -> Result<Move, UciMoveError> {
+        let invalid = || UciMoveError::Invalid(text.into());
+
+        if !text.is_ascii() || !(4..=5).contains(&text.len()) {
+            return Err(invalid());
+        }
+
+        Move::parse_square(&text[0..2]).ok_or_else(invalid)?;
+        Move::parse_square(&text[2..4]).ok_or_else(invalid)?;
+
+        if !matches!(text.get(4..), Some("" | "q" | "r" | "b" | "n")) {
+            return Err(invalid());
+        }
+
+        self.calculate_moves()
+            .into_iter()
+            .find(|r#move| r#move.to_uci(self.chess960) == text)
+            .ok_or_else(|| UciMoveError::Illegal(text.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::square;
+
+    fn parse(fen: &str, text: &str) -> Result<Move, UciMoveError> {
+        Board::from_fen(fen).parse_uci_move(text)
+    }
+
+    #[test]
+    fn flags() {
+        let fen = "r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1";
+
+        assert_eq!(
+            parse(fen, "e1g1"),
+            Ok(Move::new(square!(E1), square!(H1), MoveFlag::Castle))
+        );
+        assert_eq!(
+            parse(fen, "e1c1"),
+            Ok(Move::new(square!(E1), square!(A1), MoveFlag::Castle))
+        );
+        assert_eq!(
+            parse(fen, "e5d6"),
+            Ok(Move::new(square!(E5), square!(D6), MoveFlag::EnPassant))
+        );
+        assert_eq!(
+            parse(fen, "b7a8q"),
+            Ok(Move::new(square!(B7), square!(A8), MoveFlag::PromoteQueen))
+        );
+        assert_eq!(
+            parse(fen, "b7b8n"),
+            Ok(Move::new(square!(B7), square!(B8), MoveFlag::PromoteKnight))
+        );
+        assert_eq!(
+            parse(fen, "e1f1"),
+            Ok(Move::new(square!(E1), square!(F1), MoveFlag::None))
+        );
+
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(
+            parse(fen, "e2e4"),
+            Ok(Move::new(square!(E2), square!(E4), MoveFlag::PawnDash))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+        for text in [
+            "", "e2", "e2e", "e2e9", "i2e4", "e2e4x", "e2e4Q", "e2e4qq", "0000", "é2e4",
+        ] {
+            assert_eq!(parse(fen, text), Err(UciMoveError::Invalid(text.into())));
+        }
+    }
+
+    #[test]
+    fn illegal() {
+        let fen = "r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+        // Promotions need a piece, and non-promotions must not have one
+        for text in ["e2e4", "e1e3", "b7b8", "e1f1q", "e8g8", "a1a8q"] {
+            assert_eq!(parse(fen, text), Err(UciMoveError::Illegal(text.into())));
+        }
+    }
+}
@@ -1,14 +1,196 @@
-pub mod engine;
-pub mod interfaces;
-
-use engine::*;
-pub use therook::*;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use therook::engine::*;
+use therook::interfaces::*;
 
 fn main() {
-    let computed = Computed::new();
-    let board = Board::initial(&computed);
-    println!("{board:?}");
+    let arguments = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match arguments.first().map(String::as_str) {
+        Some("epd") => epd(&arguments[1..]),
+        Some("perft") => perft(&arguments[1..]),
+        _ => Uci::new().run(std::io::stdin().lock()),
+    }
+}
+
+fn parse_value<'a, T: FromStr>(name: &str, arguments: &mut impl Iterator<Item = &'a String>) -> T {
+    arguments
+        .next()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| exit(&format!("Missing or invalid value for {name}")))
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+// therook epd <file> [--depth N] [--nodes N] [--movetime MS] [--hash MB]
+fn epd(arguments: &[String]) {
+    let mut path = None;
+    let mut limits = SearchLimits::default();
+    let mut hash = TranspositionTable::DEFAULT_MB;
+
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--depth" => limits.depth = Some(parse_value(argument, &mut arguments)),
+            "--nodes" => limits.nodes = Some(parse_value(argument, &mut arguments)),
+            "--movetime" => {
+                limits.time = Some(Duration::from_millis(parse_value(argument, &mut arguments)))
+            }
+            "--hash" => hash = parse_value(argument, &mut arguments),
+            _ if path.is_none() => path = Some(argument),
+            _ => exit(&format!("Unknown argument {argument}")),
+        }
+    }
+
+    // Without any limit the search would never finish
+    if limits == SearchLimits::default() {
+        limits.time = Some(Duration::from_secs(1));
+    }
+
+    let path = path.unwrap_or_else(|| {
+        exit("Usage: therook epd <file> [--depth N] [--nodes N] [--movetime MS] [--hash MB]")
+    });
+    let text =
+        std::fs::read_to_string(path).unwrap_or_else(|error| exit(&format!("{path}: {error}")));
+    let suite = EpdSuite::parse(&text).unwrap_or_else(|error| exit(&format!("{path}: {error}")));
+
+    let table = TranspositionTable::try_new(hash)
+        .unwrap_or_else(|error| exit(&format!("Could not allocate {hash} MB: {error}")));
+    let table = Arc::new(table);
+    let result = suite.run(&limits, table, |epd, test| {
+        let status = match test.passed {
+            Some(true) => "passed",
+            Some(false) => "failed",
+            None => "searched",
+        };
+
+        let expected = ["bm", "am"]
+            .into_iter()
+            .filter(|opcode| epd.get_operands(opcode).is_some())
+            .map(|opcode| {
+                let moves = epd
+                    .get_moves(opcode)
+                    .into_iter()
+                    .map(|r#move| epd.board.move_to_san(r#move));
+                format!(" {opcode} {}", moves.collect::<Vec<_>>().join(" "))
+            })
+            .collect::<String>();
+
+        println!(
+            "{} {status} {}{expected} depth {} score {:?} nodes {} time {}",
+            epd.get_id().unwrap_or("?"),
+            test.search
+                .best_move
+                .map_or("0000".into(), |r#move| epd.board.move_to_san(r#move)),
+            test.search.depth,
+            test.search.score,
+            test.search.nodes,
+            test.search.time.as_millis(),
+        );
+    });
+
+    println!(
+        "Passed {}/{} nodes {} time {} nps {}",
+        result.passed,
+        result.total,
+        result.nodes,
+        result.time.as_millis(),
+        result.get_nps()
+    );
+}
+
+// therook perft <depth> [--fen FEN] [--divide] [--bisect] [--threads N] [--hash MB]
+fn perft(arguments: &[String]) {
+    let mut depth = None;
+    let mut fen = None;
+    let mut divide = false;
+    let mut bisect = false;
+    let mut threads = 1;
+    let mut hash = 0;
+
+    let mut arguments = arguments.iter().peekable();
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            // FENs are made of several words, so everything up to the next option belongs to it
+            "--fen" => {
+                let mut words = vec![];
+                while let Some(word) = arguments.next_if(|word| !word.starts_with("--")) {
+                    words.push(word.as_str());
+                }
+                fen = Some(words.join(" "));
+            }
+            "--divide" => divide = true,
+            "--bisect" => bisect = true,
+            "--threads" => threads = parse_value(argument, &mut arguments),
+            "--hash" => hash = parse_value(argument, &mut arguments),
+            _ if depth.is_none() => depth = argument.parse().ok(),
+            _ => exit(&format!("Unknown argument {argument}")),
+        }
+    }
+
+    let depth = depth.unwrap_or_else(|| {
+        exit(concat!(
+            "Usage: therook perft <depth> [--fen FEN] [--divide] [--bisect] [--threads N] ",
+            "[--hash MB]"
+        ))
+    });
+    let board = match &fen {
+        Some(fen) => {
+            Board::try_from_fen_strict(fen).unwrap_or_else(|error| exit(&format!("{fen}: {error}")))
+        }
+        None => Board::initial(),
+    };
+
+    if bisect {
+        return perft_bisect(&board, depth);
+    }
+
+    let table = (hash > 0).then(|| {
+        TranspositionTable::try_new(hash)
+            .unwrap_or_else(|error| exit(&format!("Could not allocate {hash} MB: {error}")))
+    });
+
+    let start = Instant::now();
+    let counts = board.divide_parallel(depth, threads, table.as_ref());
+    let time = start.elapsed();
+
+    // Same layout as Stockfish's go perft, so the two can be diffed
+    if divide {
+        for (r#move, count) in &counts {
+            println!("{}: {count}", r#move.to_uci(board.chess960));
+        }
+
+        println!();
+    }
+
+    // There are no root moves to split at depth 0, where the position itself is the only node
+    let nodes = match depth {
+        0 => 1,
+        _ => counts.iter().map(|(_, count)| count).sum::<u64>(),
+    };
+    let nps = nodes * 1000 / (time.as_millis() as u64).max(1);
+
+    println!("Nodes searched: {nodes}");
+    println!("Time: {}", time.as_millis());
+    println!("Nodes per second: {nps}");
+}
+
+// Stockfish is the faster oracle when it's available, the reference generator works everywhere
+fn perft_bisect(board: &Board, depth: u8) {
+    #[cfg(feature = "stockfish")]
+    let bug = match Stockfish::try_new() {
+        Some(mut stockfish) => board.bisect(&mut stockfish, depth),
+        None => board.bisect(&mut ReferenceGenerator, depth),
+    };
+    #[cfg(not(feature = "stockfish"))]
+    let bug = board.bisect(&mut ReferenceGenerator, depth);
 
-    let moves = board.calculate_moves();
-    println!("Moves[{}]: {moves:?}", moves.len());
+    match bug {
+        Some(bug) => println!("{bug}"),
+        None => println!("No difference found"),
+    }
 }
@@ -0,0 +1,40 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use therook::engine::*;
+
+// Lives in its own test binary since the allocator counts for every thread in the process
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// Once the state stack has grown to the depth searched, generating, making and undoing moves
+// shouldn't touch the heap at all
+#[test]
+fn perft_does_not_allocate() {
+    for position in PERFT_POSITIONS {
+        let mut board = Board::from_fen(position.fen);
+        let (depth, expected) = position.get_depths(100_000).last().unwrap();
+        board.perft(depth);
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let count = board.perft(depth);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+        assert_eq!(count, expected, "{}", position.name);
+        assert_eq!(allocations, 0, "{}", position.name);
+    }
+}